@@ -1,8 +1,13 @@
-use near_sdk::{near_bindgen, BorshStorageKey, require, AccountId, Timestamp, CryptoHash};
-use near_sdk::env::{block_timestamp, signer_account_id};
-use near_sdk::collections::{UnorderedMap, Vector};
+use near_sdk::{env, near_bindgen, BorshStorageKey, IntoStorageKey, require, AccountId, Balance, CurveType, Duration, Gas, Promise, PublicKey, StorageUsage, Timestamp, CryptoHash};
+use near_sdk::env::{block_timestamp, current_account_id, predecessor_account_id, signer_account_id};
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet, UnorderedMap, UnorderedSet, Vector};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::json;
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use std::collections::{HashMap, HashSet, VecDeque};
 use hex::{FromHex, encode};
+use ed25519_dalek::Verifier;
 
 // 1. Main Struct
 // Main contract structure serialized with Borsh
@@ -10,17 +15,91 @@ use hex::{FromHex, encode};
 #[derive(BorshDeserialize, BorshSerialize)]
 #[allow(non_snake_case)]
 struct RecheckRecords {
-    objectRecords: UnorderedMap<CryptoHash, ObjectRecord>,
+    objectRecords: UnorderedMap<CryptoHash, VersionedObjectRecord>,
+    legacyObjectRecords: UnorderedMap<CryptoHash, ObjectRecordV1>,
     objectSubRecords: UnorderedMap<CryptoHash, Vector<CryptoHash>>,
     trails: UnorderedMap<CryptoHash, CryptoHash>,
     e0: UnorderedMap<CryptoHash, CryptoHash>,
     e1: UnorderedMap<CryptoHash, CryptoHash>,
+    writers: UnorderedMap<AccountId, Role>,
+    storageDeposits: LookupMap<AccountId, StorageDeposit>,
+    trailRecords: UnorderedMap<CryptoHash, Vector<CryptoHash>>,
+    extra0Records: UnorderedMap<CryptoHash, Vector<CryptoHash>>,
+    extra1Records: UnorderedMap<CryptoHash, Vector<CryptoHash>>,
+    duplicateTrailPolicy: DuplicateTrailPolicy,
+    ownerId: AccountId,
+    proposedOwnerId: Option<AccountId>,
+    paused: bool,
+    stagedCode: LazyOption<Vec<u8>>,
+    stagedUpgrade: Option<StagedUpgrade>,
+    upgradeTimelock: Duration,
+    merkleRoots: LookupMap<CryptoHash, MerkleRoot>,
+    recordStats: RecordStats,
+    creatorRecordCounts: UnorderedMap<AccountId, u64>,
+    creatorRecords: UnorderedMap<AccountId, Vector<CryptoHash>>,
+    parentRecordRequired: bool,
+    maxTreeDepth: u64,
+    linkPolicies: LookupMap<CryptoHash, LinkPolicy>,
+    linkAllowlists: LookupMap<AccountId, UnorderedSet<AccountId>>,
+    signedRecordNonces: LookupMap<PublicKey, u64>,
+    sharedSubRecordsLength: u64,
+    sharedSubRecordsCleared: u64,
+    indexedLegacyRecords: LookupSet<CryptoHash>,
+    versionedLegacyRecords: u64,
+}
+
+// Contract structure of the first deployment, records are stored without a version
+#[derive(BorshDeserialize, BorshSerialize)]
+#[allow(non_snake_case)]
+struct RecheckRecordsV1 {
+    objectRecords: UnorderedMap<CryptoHash, ObjectRecordV1>,
+    objectSubRecords: UnorderedMap<CryptoHash, Vector<CryptoHash>>,
+    trails: UnorderedMap<CryptoHash, CryptoHash>,
+    e0: UnorderedMap<CryptoHash, CryptoHash>,
+    e1: UnorderedMap<CryptoHash, CryptoHash>,
+}
+
+// Contract structures that have been deployed, oldest first
+enum VersionedRecheckRecords {
+    V1(RecheckRecordsV1),
+    V2(RecheckRecords),
+}
+
+// Layout of the stored contract structure, kept under its own key next to the state.
+// The first deployment wrote no version.
+#[derive(BorshDeserialize, BorshSerialize)]
+enum StateVersion {
+    V1,
+    V2,
 }
 
 // Helper structure serialized with Borsh
 #[allow(non_snake_case)]
 #[derive(BorshDeserialize, BorshSerialize)]
 struct ObjectRecord {
+    recordId: CryptoHash,
+    parentRecordId: CryptoHash,
+    trail: CryptoHash,
+    trailSignature: Vec<u8>,
+    signerPublicKey: Option<PublicKey>,
+    creator: AccountId,
+    signer: AccountId,
+    timestamp: Timestamp,
+    extra0: CryptoHash,
+    extra1: CryptoHash,
+    extrasExplicit: bool,
+    status: RecordStatus,
+    statusTimestamp: Option<Timestamp>,
+    statusReason: Option<CryptoHash>,
+    validFrom: Option<Timestamp>,
+    validUntil: Option<Timestamp>,
+    authorPublicKey: Option<PublicKey>,
+}
+
+// Record layout of the first deployment
+#[allow(non_snake_case)]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct ObjectRecordV1 {
     recordId: CryptoHash,
     parentRecordId: CryptoHash,
     trail: CryptoHash,
@@ -31,6 +110,338 @@ struct ObjectRecord {
     extra1: CryptoHash,
 }
 
+// Records are stored with their layout version so new fields can be added without
+// breaking deserialization of records written by older code
+#[derive(BorshDeserialize, BorshSerialize)]
+enum VersionedObjectRecord {
+    V1(ObjectRecordV1),
+    V2(ObjectRecord),
+}
+
+impl From<VersionedObjectRecord> for ObjectRecord {
+    fn from(versioned_record: VersionedObjectRecord) -> Self {
+        match versioned_record {
+            VersionedObjectRecord::V1(record) => record.into(),
+            VersionedObjectRecord::V2(record) => record,
+        }
+    }
+}
+
+// createRecord and createSubRecord default both extras to the trail, the creator of the first
+// deployment was the transaction signer
+impl From<ObjectRecordV1> for ObjectRecord {
+    fn from(record: ObjectRecordV1) -> Self {
+        Self {
+            signer: record.creator.clone(),
+            extrasExplicit: record.extra0 != record.trail || record.extra1 != record.trail,
+            status: RecordStatus::Active,
+            statusTimestamp: None,
+            statusReason: None,
+            validFrom: None,
+            validUntil: None,
+            authorPublicKey: None,
+            recordId: record.recordId,
+            parentRecordId: record.parentRecordId,
+            trail: record.trail,
+            trailSignature: record.trailSignature.to_vec(),
+            signerPublicKey: None,
+            creator: record.creator,
+            timestamp: record.timestamp,
+            extra0: record.extra0,
+            extra1: record.extra1,
+        }
+    }
+}
+
+// Roles granted to accounts in the writers registry
+// Admin and Writer may create records, Auditor is read-only, Guardian may pause the contract
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    Admin,
+    Writer,
+    Auditor,
+    Guardian,
+}
+
+// Record returned by the JSON views
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RecordView {
+    record_id: String,
+    parent_record_id: String,
+    trail: String,
+    trail_signature: String,
+    signer_public_key: Option<PublicKey>,
+    creator: AccountId,
+    signer: AccountId,
+    timestamp: U64,
+    sub_records_count: U64,
+    extra0: String,
+    extra1: String,
+    extras_explicit: bool,
+    status: RecordStatus,
+    status_timestamp: Option<U64>,
+    status_reason: Option<String>,
+    valid_from: Option<U64>,
+    valid_until: Option<U64>,
+    is_valid: bool,
+    author_public_key: Option<PublicKey>,
+}
+
+// Lifecycle status of a record
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RecordStatus {
+    Active,
+    Revoked,
+    Superseded,
+    Expired,
+}
+
+// Status of a record with the time and reason of its last change
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RecordStatusView {
+    status: RecordStatus,
+    status_timestamp: Option<U64>,
+    status_reason: Option<String>,
+}
+
+// Parent record id that sub records link to but that was never anchored
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OrphanView {
+    parent_record_id: String,
+    sub_records_count: U64,
+}
+
+// Record of a subtree with its distance from the record the subtree was requested for
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreeRecordView {
+    depth: u64,
+    record: RecordView,
+}
+
+// Validity window of a record and whether it is valid at the current block
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RecordValidityView {
+    record_id: String,
+    status: RecordStatus,
+    valid_from: Option<U64>,
+    valid_until: Option<U64>,
+    is_valid: bool,
+}
+
+// Optional settings of a record created with createRecord and its variants
+#[derive(Default)]
+#[allow(non_snake_case)]
+struct RecordOptions {
+    extrasExplicit: bool,
+    signerPublicKey: Option<PublicKey>,
+    validFrom: Option<Timestamp>,
+    validUntil: Option<Timestamp>,
+    authorPublicKey: Option<PublicKey>,
+}
+
+// Whether a trail may be anchored by more than one record
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum DuplicateTrailPolicy {
+    Allow,
+    Reject,
+}
+
+// Who may link sub records to a record, records without a stored policy are CreatorOnly.
+// Allowlist accepts the accounts on the allowlist of the record creator.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum LinkPolicy {
+    CreatorOnly,
+    Allowlist,
+    Open,
+}
+
+// Trail signatures are 32 byte digests for records anchored before full signatures were
+// supported, 64 byte ed25519 signatures or 65 byte secp256k1 signatures with the recovery id
+const TRAIL_SIGNATURE_LENGTHS: [usize; 3] = [32, 64, 65];
+
+// Storage prepaid by a writer and the bytes its records consume
+#[allow(non_snake_case)]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct StorageDeposit {
+    total: Balance,
+    usedBytes: StorageUsage,
+}
+
+// NEP-145 storage balance of an account
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    total: U128,
+    available: U128,
+}
+
+// NEP-145 storage balance bounds
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    min: U128,
+    max: Option<U128>,
+}
+
+// Bytes kept as the minimum storage balance to cover an account's own deposit entry
+const STORAGE_DEPOSIT_ENTRY_BYTES: StorageUsage = 128;
+
+// Sub records can be nested this deep below their root record unless the owner sets another depth
+const DEFAULT_MAX_TREE_DEPTH: u64 = 32;
+
+// Upper bound of records per batch call. The events of a full batch of sub records created by
+// the longest account id measure about 16 KB, the log limit of a receipt.
+const MAX_BATCH_SIZE: usize = 15;
+
+// Single record of a createRecords batch
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RecordInput {
+    record_id_str: String,
+    trail_str: String,
+    trail_signature_str: String,
+}
+
+// Message an author signs to have a record created on its behalf. The signature covers the
+// sha256 of the Borsh serialized message, the contract id keeps it from being replayed on
+// other contracts and the nonce from being replayed on this one.
+#[derive(BorshSerialize)]
+struct SignedRecordMessage {
+    contract_id: AccountId,
+    nonce: u64,
+    record_id: CryptoHash,
+    parent_record_id: CryptoHash,
+    trail: CryptoHash,
+    trail_signature: Vec<u8>,
+    extra_0: CryptoHash,
+    extra_1: CryptoHash,
+}
+
+// Single record of a createSubRecordsWithExtras2 batch
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SubRecordWithExtrasInput {
+    record_id_str: String,
+    parent_record_id_str: String,
+    trail_str: String,
+    trail_signature_str: String,
+    extra_0_str: String,
+    extra_1_str: String,
+}
+
+// Outcome of a single record of a batch
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BatchItemResult {
+    record_id_str: String,
+    created: bool,
+    error: Option<String>,
+}
+
+// Hash and staging time of contract code waiting for its timelock, the code itself is
+// stored separately so it isn't loaded with the contract state
+#[allow(non_snake_case)]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct StagedUpgrade {
+    codeHash: CryptoHash,
+    stagedAt: Timestamp,
+}
+
+// Staged upgrade returned by the JSON views
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StagedUpgradeView {
+    code_hash: String,
+    staged_at: U64,
+    applicable_at: U64,
+}
+
+// Staged code can be applied 24 hours after staging unless the owner sets another timelock
+const DEFAULT_UPGRADE_TIMELOCK: Duration = 24 * 60 * 60 * 1_000_000_000;
+// Shortest timelock the owner can set, so a new upgrade always leaves time for an audit
+const MIN_UPGRADE_TIMELOCK: Duration = 60 * 60 * 1_000_000_000;
+
+// Gas attached to the migrate call that follows the code deployment
+const MIGRATE_GAS: Gas = Gas(100_000_000_000_000);
+// Gas left unused by clearIndexes and rebuildIndexes pages so they return before running out
+const INDEX_PAGE_GAS_RESERVE: Gas = Gas(20_000_000_000_000);
+
+// Running record counters, records of the first deployment are counted by rebuildIndexes
+#[allow(non_snake_case)]
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+struct RecordStats {
+    rootRecords: u64,
+    subRecords: u64,
+}
+
+// Contract statistics returned by the stats view
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StatsView {
+    total_records: U64,
+    root_records: U64,
+    sub_records: U64,
+    distinct_trails: U64,
+    distinct_creators: U64,
+}
+
+// Root of a Merkle tree of trails anchored as a whole
+#[allow(non_snake_case)]
+#[derive(BorshDeserialize, BorshSerialize)]
+struct MerkleRoot {
+    root: CryptoHash,
+    leafCount: u64,
+    metadata: CryptoHash,
+    creator: AccountId,
+    timestamp: Timestamp,
+}
+
+// Merkle root returned by the JSON views
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MerkleRootView {
+    root: String,
+    leaf_count: U64,
+    metadata: String,
+    creator: AccountId,
+    timestamp: U64,
+}
+
+// NEP-297 event standard name and version
+const EVENT_STANDARD: &str = "recheck";
+const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+// Payload of the merkle_root_created event
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct MerkleRootEventData {
+    root: String,
+    leaf_count: U64,
+    metadata: String,
+    creator: AccountId,
+    timestamp: U64,
+}
+
+// Payload of the record_created, subrecord_linked and trail_indexed events
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct RecordEventData {
+    record_id: String,
+    parent_record_id: String,
+    trail: String,
+    creator: AccountId,
+    timestamp: U64,
+}
+
 // 2. Default Implementation
 // Helper for default UnorderedMap and Vector
 #[derive(BorshStorageKey, BorshSerialize)]
@@ -41,17 +452,68 @@ pub enum StorageKeys {
     E0map,
     E1map,
     SubRecordsVector,
+    WritersMap,
+    SubRecords { parent_record_id: CryptoHash },
+    VersionedObjectRecordsMap,
+    StorageDepositsMap,
+    TrailRecordsMap,
+    Extra0RecordsMap,
+    Extra1RecordsMap,
+    TrailRecords { trail: CryptoHash },
+    Extra0Records { extra_0: CryptoHash },
+    Extra1Records { extra_1: CryptoHash },
+    StagedCode,
+    MerkleRootsMap,
+    CreatorRecordCountsMap,
+    CreatorRecordsMap,
+    CreatorRecords { creator: AccountId },
+    LinkPoliciesMap,
+    LinkAllowlistsMap,
+    LinkAllowlist { creator: AccountId },
+    SignedRecordNoncesMap,
+    IndexedLegacyRecordsSet,
 }
 
+const STATE_KEY: &[u8] = b"STATE";
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
 #[allow(non_snake_case)]
 impl Default for RecheckRecords {
     fn default() -> Self {
+        RecheckRecords::write_state_version(StateVersion::V2);
+
         Self {
-            objectRecords: UnorderedMap::new(StorageKeys::ObjectRecordsMap),
+            objectRecords: UnorderedMap::new(StorageKeys::VersionedObjectRecordsMap),
+            legacyObjectRecords: UnorderedMap::new(StorageKeys::ObjectRecordsMap),
             objectSubRecords: UnorderedMap::new(StorageKeys::ObjectSubRecordsMap),
             trails: UnorderedMap::new(StorageKeys::TrailsMap),
             e0: UnorderedMap::new(StorageKeys::E0map),
             e1: UnorderedMap::new(StorageKeys::E1map),
+            writers: UnorderedMap::new(StorageKeys::WritersMap),
+            storageDeposits: LookupMap::new(StorageKeys::StorageDepositsMap),
+            trailRecords: UnorderedMap::new(StorageKeys::TrailRecordsMap),
+            extra0Records: UnorderedMap::new(StorageKeys::Extra0RecordsMap),
+            extra1Records: UnorderedMap::new(StorageKeys::Extra1RecordsMap),
+            duplicateTrailPolicy: DuplicateTrailPolicy::Allow,
+            ownerId: current_account_id(),
+            proposedOwnerId: None,
+            paused: false,
+            stagedCode: LazyOption::new(StorageKeys::StagedCode, None),
+            stagedUpgrade: None,
+            upgradeTimelock: DEFAULT_UPGRADE_TIMELOCK,
+            merkleRoots: LookupMap::new(StorageKeys::MerkleRootsMap),
+            recordStats: RecordStats::default(),
+            creatorRecordCounts: UnorderedMap::new(StorageKeys::CreatorRecordCountsMap),
+            creatorRecords: UnorderedMap::new(StorageKeys::CreatorRecordsMap),
+            parentRecordRequired: true,
+            maxTreeDepth: DEFAULT_MAX_TREE_DEPTH,
+            linkPolicies: LookupMap::new(StorageKeys::LinkPoliciesMap),
+            linkAllowlists: LookupMap::new(StorageKeys::LinkAllowlistsMap),
+            signedRecordNonces: LookupMap::new(StorageKeys::SignedRecordNoncesMap),
+            sharedSubRecordsLength: 0,
+            sharedSubRecordsCleared: 0,
+            indexedLegacyRecords: LookupSet::new(StorageKeys::IndexedLegacyRecordsSet),
+            versionedLegacyRecords: 0,
         }
     }
 }
@@ -62,304 +524,3925 @@ impl RecheckRecords {
     fn only_unique_records(contract_self: &RecheckRecords, record_id_str: &String) -> CryptoHash {
         let record_id: CryptoHash = <CryptoHash>::from_hex(record_id_str)
             .expect("Invalid recordId hash.");
-        require!(contract_self.objectRecords.get(&record_id).is_none(), "Record must be unique.");
+        require!(RecheckRecords::get_record(contract_self, &record_id).is_none(), "Record must be unique.");
         return record_id;
     }
 
-    fn string_to_hex_bytes(str: String) -> CryptoHash {
-        let hex_bytes: CryptoHash = <CryptoHash>::from_hex(str).expect("Invalid hex string.");
-        return hex_bytes;
-    }
+    // Records written before versioning stay in the legacy map and are read from there
+    fn get_record(contract_self: &RecheckRecords, record_id: &CryptoHash) -> Option<ObjectRecord> {
+        if let Some(versioned_record) = contract_self.objectRecords.get(record_id) {
+            return Some(versioned_record.into());
+        }
 
-    fn hex_bytes_to_string(hex_bytes: CryptoHash) -> String {
-        let string: String = encode(hex_bytes);
-        return string;
+        return contract_self.legacyObjectRecords.get(record_id).map(|record| record.into());
     }
 
-    fn null_record() -> (String,
-                         String,
-                         String,
-                         String,
-                         AccountId,
-                         Timestamp,
-                         u64) {
-        let null_account_id: AccountId = "none.none".parse().unwrap();
+    // Records are indexed with legacy records first, followed by versioned records. Versioned
+    // copies of legacy records are read at the legacy position and return None at their own.
+    fn get_record_at(contract_self: &RecheckRecords, index: u64) -> Option<ObjectRecord> {
+        let legacy_records_length: u64 = contract_self.legacyObjectRecords.len();
 
-        return (
-            String::from("0"),
-            String::from("0"),
-            String::from("0"),
-            String::from("0"),
-            null_account_id,
-            0,
-            0
-        );
+        if index < legacy_records_length {
+            let record_id: CryptoHash = contract_self.legacyObjectRecords.keys_as_vector().get(index)?;
+            return RecheckRecords::get_record(contract_self, &record_id);
+        }
+
+        let record_id: CryptoHash = contract_self.objectRecords.keys_as_vector().get(index - legacy_records_length)?;
+        if contract_self.legacyObjectRecords.get(&record_id).is_some() {
+            return None;
+        }
+
+        return contract_self.objectRecords.get(&record_id).map(|versioned_record| versioned_record.into());
     }
-}
 
-//Write functions
-#[near_bindgen]
-#[allow(non_snake_case)]
-impl RecheckRecords {
-    #[private]
-    #[allow(non_snake_case)]
-    pub fn createSubRecordWithExtras2(&mut self,
-                                      record_id_str: String,
-                                      parent_record_id_str: String,
-                                      trail_str: String,
-                                      trail_signature_str: String,
-                                      extra_0_str: String,
-                                      extra_1_str: String) {
-        let record_id: CryptoHash = RecheckRecords::only_unique_records(&self, &record_id_str);
+    fn records_length(contract_self: &RecheckRecords) -> u64 {
+        return contract_self.legacyObjectRecords.len() + contract_self.objectRecords.len();
+    }
 
-        let parent_record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(parent_record_id_str);
-        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
-        let trail_signature: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_signature_str);
-        let extra_0: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_0_str);
-        let extra_1: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_1_str);
+    fn records_count(contract_self: &RecheckRecords) -> u64 {
+        return RecheckRecords::records_length(contract_self) - contract_self.versionedLegacyRecords;
+    }
 
-        let record = ObjectRecord {
-            recordId: record_id,
-            parentRecordId: parent_record_id,
-            trail,
-            trailSignature: trail_signature,
-            creator: signer_account_id(),
-            timestamp: block_timestamp(),
-            extra0: extra_0,
-            extra1: extra_1,
+    fn read_state() -> VersionedRecheckRecords {
+        let state: Vec<u8> = env::storage_read(STATE_KEY).expect("No contract state to migrate.");
+        let state_version: StateVersion = match env::storage_read(STATE_VERSION_KEY) {
+            Some(state_version) => StateVersion::try_from_slice(&state_version).expect("Unknown contract state version."),
+            None => StateVersion::V1,
+        };
+
+        return match state_version {
+            StateVersion::V1 => VersionedRecheckRecords::V1(RecheckRecordsV1::try_from_slice(&state)
+                .expect("Unknown contract state.")),
+            StateVersion::V2 => VersionedRecheckRecords::V2(RecheckRecords::try_from_slice(&state)
+                .expect("Unknown contract state.")),
         };
+    }
 
-        self.objectRecords.insert(&record_id, &record);
+    fn write_state_version(state_version: StateVersion) {
+        env::storage_write(STATE_VERSION_KEY, &state_version.try_to_vec().expect("Cannot serialize the state version."));
+    }
 
-        if record_id != parent_record_id {
-            if self.objectSubRecords.get(&parent_record_id).is_none() {
-                let sub_records_empty_vec: Vector<CryptoHash> = Vector::new(StorageKeys::SubRecordsVector);
-                self.objectSubRecords.insert(&parent_record_id, &sub_records_empty_vec);
-            }
+    fn only_writers(contract_self: &RecheckRecords) {
+        require!(!contract_self.paused, "The contract is paused.");
 
-            let mut new_sub_records: Vector<CryptoHash> = self.objectSubRecords.get(&parent_record_id)
-                .expect("No parent record found!!!");
-            new_sub_records.push(&record_id);
+        let caller: AccountId = predecessor_account_id();
 
-            self.objectSubRecords.insert(&parent_record_id, &new_sub_records);
+        if caller == current_account_id() {
+            return;
         }
 
-        self.trails.insert(&trail, &record_id);
-        self.e0.insert(&extra_0, &record_id);
-        self.e1.insert(&extra_1, &record_id);
+        let can_write: bool = match contract_self.writers.get(&caller) {
+            Some(Role::Admin) | Some(Role::Writer) => true,
+            _ => false,
+        };
+        require!(can_write, "Only authorized writers can create records.");
     }
 
-    #[private]
-    #[allow(non_snake_case)]
-    pub fn createSubRecord(&mut self,
-                           record_id_str: String,
-                           parent_record_id_str: String,
-                           trail_str: String,
-                           trail_signature_str: String) {
-        RecheckRecords::only_unique_records(&self, &record_id_str);
-
-        let extra_0_str: String = trail_str.clone();
-        let extra_1_str: String = trail_str.clone();
-
-        return RecheckRecords::createSubRecordWithExtras2(self,
-                                                          record_id_str,
-                                                          parent_record_id_str,
-                                                          trail_str,
-                                                          trail_signature_str,
-                                                          extra_0_str,
-                                                          extra_1_str);
+    fn only_owner(contract_self: &RecheckRecords) {
+        require!(predecessor_account_id() == contract_self.ownerId, "Only the owner can call this method.");
     }
 
-    #[private]
-    #[allow(non_snake_case)]
-    pub fn createRecord(&mut self,
-                        record_id_str: String,
-                        trail_str: String,
-                        trail_signature_str: String) {
-        RecheckRecords::only_unique_records(&self, &record_id_str);
+    fn only_owner_or_guardian(contract_self: &RecheckRecords) {
+        let caller: AccountId = predecessor_account_id();
 
-        let parent_record_id_str: String = record_id_str.clone();
-        let extra_0_str: String = trail_str.clone();
-        let extra_1_str: String = trail_str.clone();
+        require!(caller == contract_self.ownerId || contract_self.writers.get(&caller) == Some(Role::Guardian),
+            "Only the owner or a guardian can call this method.");
+    }
 
-        return RecheckRecords::createSubRecordWithExtras2(self,
-                                                          record_id_str,
-                                                          parent_record_id_str,
-                                                          trail_str,
-                                                          trail_signature_str,
-                                                          extra_0_str,
-                                                          extra_1_str);
+    fn is_admin(contract_self: &RecheckRecords, account_id: &AccountId) -> bool {
+        return *account_id == current_account_id()
+            || *account_id == contract_self.ownerId
+            || contract_self.writers.get(account_id) == Some(Role::Admin);
     }
-}
 
-//Read-only functions
-#[near_bindgen]
-#[allow(non_snake_case)]
-impl RecheckRecords {
-    #[allow(non_snake_case)]
-    pub fn records(self, record_id_str: String) -> (String,
-                                                    String,
-                                                    String,
-                                                    String,
-                                                    AccountId,
-                                                    Timestamp,
-                                                    u64) {
-        let record_id_hash: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+    // Updated legacy records are copied to the versioned records, get_record prefers the copy.
+    // The legacy entry keeps its position so rebuildIndexes pages stay stable.
+    fn save_record(contract_self: &mut RecheckRecords, record: ObjectRecord) {
+        let record_id: CryptoHash = record.recordId;
+        let previous_record: Option<VersionedObjectRecord> = contract_self.objectRecords
+            .insert(&record_id, &VersionedObjectRecord::V2(record));
 
-        if self.objectRecords.get(&record_id_hash).is_none() {
-            return RecheckRecords::null_record();
+        if previous_record.is_none() && contract_self.legacyObjectRecords.get(&record_id).is_some() {
+            contract_self.versionedLegacyRecords += 1;
         }
+    }
 
+    fn change_record_status(contract_self: &mut RecheckRecords,
+                            record_id_str: String,
+                            reason_hash_str: String,
+                            status: RecordStatus) {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+        let reason_hash: CryptoHash = RecheckRecords::string_to_hex_bytes(reason_hash_str);
+        let caller: AccountId = predecessor_account_id();
 
-        let record: ObjectRecord = self.objectRecords.get(&record_id_hash)
+        let mut record: ObjectRecord = RecheckRecords::get_record(contract_self, &record_id)
             .expect("None existing record");
 
-        let record_id: String = RecheckRecords::hex_bytes_to_string(record.recordId);
-        let parent_record_id: String = RecheckRecords::hex_bytes_to_string(record.parentRecordId);
-        let trail: String = RecheckRecords::hex_bytes_to_string(record.trail);
-        let trail_signature: String = RecheckRecords::hex_bytes_to_string(record.trailSignature);
-        let creator: AccountId = record.creator;
-        let timestamp: Timestamp = record.timestamp;
-        let mut sub_records_length: u64 = 0;
+        require!(record.creator == caller || RecheckRecords::is_admin(contract_self, &caller),
+            "Only the record creator or an admin can change the record status.");
+        require!(record.status != RecordStatus::Revoked, "Record is already revoked.");
 
-        if !self.objectSubRecords.get(&record_id_hash).is_none() {
-            sub_records_length = self.objectSubRecords.get(&record_id_hash).unwrap().len();
-        }
+        let creator: AccountId = record.creator.clone();
+        let storage_usage_before: StorageUsage = env::storage_usage();
 
-        return (record_id, parent_record_id, trail, trail_signature, creator, timestamp, sub_records_length);
-    }
+        record.status = status;
+        record.statusTimestamp = Some(block_timestamp());
+        record.statusReason = Some(reason_hash);
 
-    #[allow(non_snake_case)]
-    pub fn subRecord(self, sub_record_id_str: String, index: u64) -> (String,
-                                                                      String,
-                                                                      String,
-                                                                      String,
-                                                                      AccountId,
-                                                                      Timestamp,
-                                                                      u64) {
-        let sub_record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(sub_record_id_str);
+        let event: &str = match status {
+            RecordStatus::Revoked => "record_revoked",
+            _ => "record_superseded",
+        };
+        RecheckRecords::emit_record_event(event, &record);
+        RecheckRecords::save_record(contract_self, record);
 
-        if self.objectSubRecords.get(&sub_record_id).is_none() {
-            return RecheckRecords::null_record();
+        // The record's storage belongs to its creator, whoever changes the status
+        let storage_used: StorageUsage = env::storage_usage().saturating_sub(storage_usage_before);
+        if storage_used > 0 {
+            RecheckRecords::charge_storage(contract_self, &creator, storage_used);
         }
+    }
 
+    // Every key gets its own vector prefix so record lists don't overwrite each other
+    fn push_to_index<K: BorshSerialize + BorshDeserialize>(index: &mut UnorderedMap<K, Vector<CryptoHash>>,
+                                                           key: &K,
+                                                           storage_key: StorageKeys,
+                                                           record_id: &CryptoHash) {
+        let mut record_ids: Vector<CryptoHash> = index.get(key)
+            .unwrap_or_else(|| Vector::new(storage_key));
+        record_ids.push(record_id);
 
-        let sub_records: Vector<CryptoHash> = self.objectSubRecords.get(&sub_record_id)
-            .expect("None existing sub record");
+        index.insert(key, &record_ids);
+    }
 
-        if !sub_records.get(index).is_none() {
-            return RecheckRecords::null_record();
+    fn index_sub_record(contract_self: &mut RecheckRecords, record: &ObjectRecord) {
+        if record.recordId != record.parentRecordId {
+            RecheckRecords::push_to_index(&mut contract_self.objectSubRecords,
+                                          &record.parentRecordId,
+                                          StorageKeys::SubRecords { parent_record_id: record.parentRecordId },
+                                          &record.recordId);
         }
+    }
 
-        let sub_record_id_str: String = RecheckRecords::hex_bytes_to_string(sub_record_id);
-
-        return RecheckRecords::records(self, sub_record_id_str);
+    fn index_record(contract_self: &mut RecheckRecords, record: &ObjectRecord) {
+        RecheckRecords::push_to_index(&mut contract_self.trailRecords,
+                                      &record.trail,
+                                      StorageKeys::TrailRecords { trail: record.trail },
+                                      &record.recordId);
+        RecheckRecords::push_to_index(&mut contract_self.extra0Records,
+                                      &record.extra0,
+                                      StorageKeys::Extra0Records { extra_0: record.extra0 },
+                                      &record.recordId);
+        RecheckRecords::push_to_index(&mut contract_self.extra1Records,
+                                      &record.extra1,
+                                      StorageKeys::Extra1Records { extra_1: record.extra1 },
+                                      &record.recordId);
     }
 
-    #[allow(non_snake_case)]
-    pub fn verifyTrail(self, trail_str: String) -> (String,
-                                                    String,
-                                                    String,
-                                                    String,
-                                                    AccountId,
-                                                    Timestamp,
-                                                    u64) {
-        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
+    // Creator lists can grow long, so records are appended without looking for existing entries
+    fn index_creator(contract_self: &mut RecheckRecords, record: &ObjectRecord) {
+        RecheckRecords::push_to_index(&mut contract_self.creatorRecords,
+                                      &record.creator,
+                                      StorageKeys::CreatorRecords { creator: record.creator.clone() },
+                                      &record.recordId);
+    }
 
-        if self.trails.get(&trail).is_none() {
-            return RecheckRecords::null_record();
+    fn count_record(contract_self: &mut RecheckRecords, record: &ObjectRecord) {
+        if record.recordId == record.parentRecordId {
+            contract_self.recordStats.rootRecords += 1;
+        } else {
+            contract_self.recordStats.subRecords += 1;
         }
 
+        let creator_records_count: u64 = contract_self.creatorRecordCounts.get(&record.creator).unwrap_or(0);
+        contract_self.creatorRecordCounts.insert(&record.creator, &(creator_records_count + 1));
+    }
 
-        let record_id: CryptoHash = self.trails.get(&trail)
-            .expect("None existing record");
+    // Parents that are part of the same batch are looked up in batch_parents. Ancestors above
+    // the parent may be missing for records imported while parents weren't required.
+    fn check_parent_record(contract_self: &RecheckRecords,
+                           record_id: &CryptoHash,
+                           parent_record_id: &CryptoHash,
+                           batch_parents: &HashMap<CryptoHash, CryptoHash>) -> Result<(), String> {
+        if record_id == parent_record_id {
+            return Ok(());
+        }
 
-        let record_id_str: String = RecheckRecords::hex_bytes_to_string(record_id);
+        let mut depth: u64 = 1;
+        let mut current_record_id: CryptoHash = *parent_record_id;
+
+        loop {
+            let next_record_id: Option<CryptoHash> = match batch_parents.get(&current_record_id) {
+                Some(batch_parent_record_id) => Some(*batch_parent_record_id),
+                None => RecheckRecords::get_record(contract_self, &current_record_id)
+                    .map(|record| record.parentRecordId),
+            };
+
+            let next_record_id: CryptoHash = match next_record_id {
+                Some(next_record_id) => next_record_id,
+                None if depth == 1 && contract_self.parentRecordRequired => {
+                    return Err(String::from("Parent record must exist."));
+                }
+                None => return Ok(()),
+            };
+
+            if next_record_id == current_record_id {
+                return Ok(());
+            }
+            if next_record_id == *record_id {
+                return Err(String::from("Record can't be its own ancestor."));
+            }
 
-        return RecheckRecords::records(self, record_id_str);
+            depth += 1;
+            if depth > contract_self.maxTreeDepth {
+                return Err(String::from("Record tree is too deep."));
+            }
+            current_record_id = next_record_id;
+        }
     }
 
-    #[allow(non_snake_case)]
-    pub fn verifyExtra0(self, extra_0_str: String) -> (String,
-                                                       String,
-                                                       String,
-                                                       String,
-                                                       AccountId,
-                                                       Timestamp,
-                                                       u64) {
+    // Parents that aren't anchored yet were already checked by check_parent_record
+    fn check_link_policy(contract_self: &RecheckRecords,
+                         record_id: &CryptoHash,
+                         parent_record_id: &CryptoHash,
+                         account_id: &AccountId) -> Result<(), String> {
+        if record_id == parent_record_id {
+            return Ok(());
+        }
+
+        let parent_record: ObjectRecord = match RecheckRecords::get_record(contract_self, parent_record_id) {
+            Some(parent_record) => parent_record,
+            None => return Ok(()),
+        };
+
+        if !RecheckRecords::can_link(contract_self, &parent_record, account_id) {
+            return Err(String::from("Not allowed to link sub records to the parent record."));
+        }
+
+        return Ok(());
+    }
+
+    fn can_link(contract_self: &RecheckRecords, parent_record: &ObjectRecord, account_id: &AccountId) -> bool {
+        if parent_record.creator == *account_id {
+            return true;
+        }
+
+        return match RecheckRecords::link_policy(contract_self, &parent_record.recordId) {
+            LinkPolicy::CreatorOnly => false,
+            LinkPolicy::Allowlist => contract_self.linkAllowlists.get(&parent_record.creator)
+                .map(|allowlist| allowlist.contains(account_id))
+                .unwrap_or(false),
+            LinkPolicy::Open => true,
+        };
+    }
+
+    fn link_policy(contract_self: &RecheckRecords, record_id: &CryptoHash) -> LinkPolicy {
+        return contract_self.linkPolicies.get(record_id).unwrap_or(LinkPolicy::CreatorOnly);
+    }
+
+    fn check_duplicate_trail(contract_self: &RecheckRecords,
+                             trail: &CryptoHash,
+                             batch_trails: &HashSet<CryptoHash>) -> Result<(), String> {
+        if contract_self.duplicateTrailPolicy == DuplicateTrailPolicy::Reject
+            && (batch_trails.contains(trail) || contract_self.trails.get(trail).is_some()) {
+            return Err(String::from("Trail must be unique."));
+        }
+
+        return Ok(());
+    }
+
+    fn index_page<K: BorshSerialize + BorshDeserialize>(contract_self: &RecheckRecords,
+                                                        index: &UnorderedMap<K, Vector<CryptoHash>>,
+                                                        key: &K,
+                                                        from_index: u64,
+                                                        limit: u64) -> Vec<RecordView> {
+        let record_ids: Vector<CryptoHash> = match index.get(key) {
+            Some(record_ids) => record_ids,
+            None => return vec![],
+        };
+
+        return record_ids.iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .filter_map(|record_id| RecheckRecords::record_view(contract_self, &record_id))
+            .collect();
+    }
+
+    fn create_record(contract_self: &mut RecheckRecords,
+                     record_id_str: String,
+                     parent_record_id_str: String,
+                     trail_str: String,
+                     trail_signature_str: String,
+                     extra_0_str: String,
+                     extra_1_str: String,
+                     options: RecordOptions) {
+        RecheckRecords::only_writers(contract_self);
+        let record_id: CryptoHash = RecheckRecords::only_unique_records(contract_self, &record_id_str);
+
+        let parent_record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(parent_record_id_str);
+        RecheckRecords::check_parent_record(contract_self, &record_id, &parent_record_id, &HashMap::new())
+            .unwrap_or_else(|error| panic!("{}", error));
+        RecheckRecords::check_link_policy(contract_self, &record_id, &parent_record_id, &predecessor_account_id())
+            .unwrap_or_else(|error| panic!("{}", error));
+        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
+        RecheckRecords::check_duplicate_trail(contract_self, &trail, &HashSet::new())
+            .unwrap_or_else(|error| panic!("{}", error));
+        let trail_signature: Vec<u8> = RecheckRecords::parse_signature(&trail_signature_str)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        if let Some(public_key) = &options.signerPublicKey {
+            let expected_length: usize = match public_key.curve_type() {
+                CurveType::ED25519 => 64,
+                CurveType::SECP256K1 => 65,
+            };
+            require!(trail_signature.len() == expected_length, "Trail signature doesn't match the public key curve.");
+        }
+
+        if let (Some(valid_from), Some(valid_until)) = (options.validFrom, options.validUntil) {
+            require!(valid_from < valid_until, "validFrom must be before validUntil.");
+        }
+
         let extra_0: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_0_str);
+        let extra_1: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_1_str);
+
+        let record = ObjectRecord {
+            recordId: record_id,
+            parentRecordId: parent_record_id,
+            trail,
+            trailSignature: trail_signature,
+            signerPublicKey: options.signerPublicKey,
+            creator: predecessor_account_id(),
+            signer: signer_account_id(),
+            timestamp: block_timestamp(),
+            extra0: extra_0,
+            extra1: extra_1,
+            extrasExplicit: options.extrasExplicit,
+            status: RecordStatus::Active,
+            statusTimestamp: None,
+            statusReason: None,
+            validFrom: options.validFrom,
+            validUntil: options.validUntil,
+            authorPublicKey: options.authorPublicKey,
+        };
+
+        RecheckRecords::emit_created_records_events(&[&record]);
+        RecheckRecords::insert_record(contract_self, record);
+    }
+
+    // Events are emitted by the caller, once for a single record or a whole batch
+    fn insert_record(contract_self: &mut RecheckRecords, record: ObjectRecord) {
+        let storage_usage_before: StorageUsage = env::storage_usage();
+        let creator: AccountId = record.creator.clone();
+
+        RecheckRecords::index_sub_record(contract_self, &record);
+        RecheckRecords::index_record(contract_self, &record);
+        RecheckRecords::index_creator(contract_self, &record);
+        RecheckRecords::count_record(contract_self, &record);
+
+        contract_self.trails.insert(&record.trail, &record.recordId);
+        contract_self.e0.insert(&record.extra0, &record.recordId);
+        contract_self.e1.insert(&record.extra1, &record.recordId);
+
+        let record_id: CryptoHash = record.recordId;
+        contract_self.objectRecords.insert(&record_id, &VersionedObjectRecord::V2(record));
+
+        let storage_used: StorageUsage = env::storage_usage().saturating_sub(storage_usage_before);
+        RecheckRecords::charge_storage(contract_self, &creator, storage_used);
+    }
+
+    // Records anchored by the contract account itself are paid from the contract balance
+    fn charge_storage(contract_self: &mut RecheckRecords, account_id: &AccountId, storage_used: StorageUsage) {
+        if *account_id == current_account_id() {
+            return;
+        }
+
+        let mut deposit: StorageDeposit = contract_self.storageDeposits.get(account_id)
+            .expect("The account is not registered for storage.");
+        deposit.usedBytes += storage_used;
+
+        let required: Balance = RecheckRecords::storage_cost(STORAGE_DEPOSIT_ENTRY_BYTES + deposit.usedBytes);
+        require!(required <= deposit.total, "Insufficient storage deposit.");
+
+        contract_self.storageDeposits.insert(account_id, &deposit);
+    }
+
+    fn storage_cost(bytes: StorageUsage) -> Balance {
+        return Balance::from(bytes) * env::storage_byte_cost();
+    }
+
+    fn storage_balance(deposit: &StorageDeposit) -> StorageBalance {
+        let locked: Balance = RecheckRecords::storage_cost(STORAGE_DEPOSIT_ENTRY_BYTES + deposit.usedBytes);
+
+        return StorageBalance {
+            total: U128(deposit.total),
+            available: U128(deposit.total.saturating_sub(locked)),
+        };
+    }
+
+    fn parse_hash(hash_str: &String, field: &str) -> Result<CryptoHash, String> {
+        return <CryptoHash>::from_hex(hash_str).map_err(|_| format!("Invalid {} hash.", field));
+    }
+
+    fn parse_signature(signature_str: &String) -> Result<Vec<u8>, String> {
+        let signature: Vec<u8> = <Vec<u8>>::from_hex(signature_str)
+            .map_err(|_| String::from("Invalid trailSignature hash."))?;
+
+        if !TRAIL_SIGNATURE_LENGTHS.contains(&signature.len()) {
+            return Err(String::from("Invalid trailSignature length."));
+        }
+
+        return Ok(signature);
+    }
+
+    // ed25519 signatures are checked in the contract, secp256k1 signatures are checked by
+    // recovering the signer key from the 32 byte message with the recovery id as last byte
+    fn verify_signature(message: &[u8], signature: &[u8], public_key: &PublicKey) -> bool {
+        let public_key_bytes: &[u8] = &public_key.as_bytes()[1..];
+
+        return match public_key.curve_type() {
+            CurveType::ED25519 => {
+                let ed25519_public_key = match ed25519_dalek::PublicKey::from_bytes(public_key_bytes) {
+                    Ok(ed25519_public_key) => ed25519_public_key,
+                    Err(_) => return false,
+                };
+                let ed25519_signature = match ed25519_dalek::Signature::from_bytes(signature) {
+                    Ok(ed25519_signature) => ed25519_signature,
+                    Err(_) => return false,
+                };
+
+                ed25519_public_key.verify(message, &ed25519_signature).is_ok()
+            }
+            CurveType::SECP256K1 => {
+                if message.len() != 32 || signature.len() != 65 {
+                    return false;
+                }
+
+                env::ecrecover(message, &signature[..64], signature[64], false)
+                    .map(|recovered_public_key| recovered_public_key[..] == public_key_bytes[..])
+                    .unwrap_or(false)
+            }
+        };
+    }
+
+    fn signed_record_message(payload: &SubRecordWithExtrasInput, nonce: u64) -> CryptoHash {
+        let message = SignedRecordMessage {
+            contract_id: current_account_id(),
+            nonce,
+            record_id: RecheckRecords::string_to_hex_bytes(payload.record_id_str.clone()),
+            parent_record_id: RecheckRecords::string_to_hex_bytes(payload.parent_record_id_str.clone()),
+            trail: RecheckRecords::string_to_hex_bytes(payload.trail_str.clone()),
+            trail_signature: RecheckRecords::parse_signature(&payload.trail_signature_str)
+                .unwrap_or_else(|error| panic!("{}", error)),
+            extra_0: RecheckRecords::string_to_hex_bytes(payload.extra_0_str.clone()),
+            extra_1: RecheckRecords::string_to_hex_bytes(payload.extra_1_str.clone()),
+        };
+
+        return env::sha256_array(&message.try_to_vec().expect("Failed to serialize the signed record message."));
+    }
+
+    fn parse_record_input(contract_self: &RecheckRecords,
+                          input: &SubRecordWithExtrasInput,
+                          extras_explicit: bool,
+                          batch_parents: &HashMap<CryptoHash, CryptoHash>,
+                          batch_trails: &HashSet<CryptoHash>) -> Result<ObjectRecord, String> {
+        let record_id: CryptoHash = RecheckRecords::parse_hash(&input.record_id_str, "recordId")?;
+
+        if batch_parents.contains_key(&record_id) || RecheckRecords::get_record(contract_self, &record_id).is_some() {
+            return Err(String::from("Record must be unique."));
+        }
+
+        let parent_record_id: CryptoHash = RecheckRecords::parse_hash(&input.parent_record_id_str, "parentRecordId")?;
+        RecheckRecords::check_parent_record(contract_self, &record_id, &parent_record_id, batch_parents)?;
+        RecheckRecords::check_link_policy(contract_self, &record_id, &parent_record_id, &predecessor_account_id())?;
+
+        let trail: CryptoHash = RecheckRecords::parse_hash(&input.trail_str, "trail")?;
+        RecheckRecords::check_duplicate_trail(contract_self, &trail, batch_trails)?;
+
+        return Ok(ObjectRecord {
+            recordId: record_id,
+            parentRecordId: parent_record_id,
+            trail,
+            trailSignature: RecheckRecords::parse_signature(&input.trail_signature_str)?,
+            signerPublicKey: None,
+            creator: predecessor_account_id(),
+            signer: signer_account_id(),
+            timestamp: block_timestamp(),
+            extra0: RecheckRecords::parse_hash(&input.extra_0_str, "extra0")?,
+            extra1: RecheckRecords::parse_hash(&input.extra_1_str, "extra1")?,
+            extrasExplicit: extras_explicit,
+            status: RecordStatus::Active,
+            statusTimestamp: None,
+            statusReason: None,
+            validFrom: None,
+            validUntil: None,
+            authorPublicKey: None,
+        });
+    }
+
+    // Validates the whole batch before writing, invalid items are reported and skipped. Storage is
+    // charged per written record, a deposit shortfall aborts the whole batch.
+    fn insert_records_batch(contract_self: &mut RecheckRecords,
+                            inputs: Vec<SubRecordWithExtrasInput>,
+                            extras_explicit: bool) -> Vec<BatchItemResult> {
+        RecheckRecords::only_writers(contract_self);
+        require!(!inputs.is_empty() && inputs.len() <= MAX_BATCH_SIZE,
+            format!("Batch must contain between 1 and {} records.", MAX_BATCH_SIZE));
+
+        let mut batch_parents: HashMap<CryptoHash, CryptoHash> = HashMap::new();
+        let mut batch_trails: HashSet<CryptoHash> = HashSet::new();
+        let mut parsed_records: Vec<Result<ObjectRecord, String>> = Vec::with_capacity(inputs.len());
+
+        for input in inputs.iter() {
+            let parsed_record = RecheckRecords::parse_record_input(contract_self,
+                                                                   input,
+                                                                   extras_explicit,
+                                                                   &batch_parents,
+                                                                   &batch_trails);
+
+            if let Ok(record) = &parsed_record {
+                batch_parents.insert(record.recordId, record.parentRecordId);
+                batch_trails.insert(record.trail);
+            }
+            parsed_records.push(parsed_record);
+        }
+
+        let created_records: Vec<&ObjectRecord> = parsed_records.iter()
+            .filter_map(|parsed_record| parsed_record.as_ref().ok())
+            .collect();
+        if !created_records.is_empty() {
+            RecheckRecords::emit_created_records_events(&created_records);
+        }
+
+        let mut results: Vec<BatchItemResult> = Vec::with_capacity(inputs.len());
+
+        for (input, parsed_record) in inputs.into_iter().zip(parsed_records) {
+            match parsed_record {
+                Ok(record) => {
+                    RecheckRecords::insert_record(contract_self, record);
+                    results.push(BatchItemResult { record_id_str: input.record_id_str, created: true, error: None });
+                }
+                Err(error) => {
+                    results.push(BatchItemResult { record_id_str: input.record_id_str, created: false, error: Some(error) });
+                }
+            }
+        }
+
+        return results;
+    }
+
+    fn record_event_data(record: &ObjectRecord) -> RecordEventData {
+        return RecordEventData {
+            record_id: RecheckRecords::hex_bytes_to_string(record.recordId),
+            parent_record_id: RecheckRecords::hex_bytes_to_string(record.parentRecordId),
+            trail: RecheckRecords::hex_bytes_to_string(record.trail),
+            creator: record.creator.clone(),
+            timestamp: U64(record.timestamp),
+        };
+    }
+
+    fn emit_record_event(event: &str, record: &ObjectRecord) {
+        RecheckRecords::emit_event(event, &[RecheckRecords::record_event_data(record)]);
+    }
+
+    // Each event lists all created records, so a batch logs three events instead of three per record
+    fn emit_created_records_events(records: &[&ObjectRecord]) {
+        let created: Vec<RecordEventData> = records.iter()
+            .map(|record| RecheckRecords::record_event_data(record))
+            .collect();
+        let linked: Vec<RecordEventData> = records.iter()
+            .filter(|record| record.recordId != record.parentRecordId)
+            .map(|record| RecheckRecords::record_event_data(record))
+            .collect();
+
+        RecheckRecords::emit_event("record_created", &created);
+        if !linked.is_empty() {
+            RecheckRecords::emit_event("subrecord_linked", &linked);
+        }
+        RecheckRecords::emit_event("trail_indexed", &created);
+    }
+
+    fn emit_event<T: Serialize>(event: &str, data: &[T]) {
+        let event = json!({
+            "standard": EVENT_STANDARD,
+            "version": EVENT_STANDARD_VERSION,
+            "event": event,
+            "data": data,
+        });
+
+        env::log_str(&format!("EVENT_JSON:{}", event));
+    }
+
+    // Leaves are the trail hashes themselves, every parent node is the sha256 of its left and
+    // right child concatenated and a node without a right sibling is paired with itself.
+    // Bit n of the leaf index tells whether the node at level n is a right child.
+    fn compute_merkle_root(leaf: CryptoHash, proof: &Vec<CryptoHash>, index: u64) -> CryptoHash {
+        let mut node: CryptoHash = leaf;
+        let mut node_index: u64 = index;
+
+        for sibling in proof.iter() {
+            let mut pair: Vec<u8> = Vec::with_capacity(64);
+
+            if node_index & 1 == 0 {
+                pair.extend_from_slice(&node);
+                pair.extend_from_slice(sibling);
+            } else {
+                pair.extend_from_slice(sibling);
+                pair.extend_from_slice(&node);
+            }
+
+            node = env::sha256_array(&pair);
+            node_index >>= 1;
+        }
+
+        return node;
+    }
+
+    fn string_to_hex_bytes(str: String) -> CryptoHash {
+        let hex_bytes: CryptoHash = <CryptoHash>::from_hex(str).expect("Invalid hex string.");
+        return hex_bytes;
+    }
+
+    fn hex_bytes_to_string(hex_bytes: CryptoHash) -> String {
+        let string: String = encode(hex_bytes);
+        return string;
+    }
+
+    fn record_tuple(contract_self: &RecheckRecords, record_id_hash: &CryptoHash) -> (String,
+                                                                                     String,
+                                                                                     String,
+                                                                                     String,
+                                                                                     AccountId,
+                                                                                     Timestamp,
+                                                                                     u64) {
+        if RecheckRecords::get_record(contract_self, record_id_hash).is_none() {
+            return RecheckRecords::null_record();
+        }
+
+
+        let record: ObjectRecord = RecheckRecords::get_record(contract_self, record_id_hash)
+            .expect("None existing record");
+
+        let record_id: String = RecheckRecords::hex_bytes_to_string(record.recordId);
+        let parent_record_id: String = RecheckRecords::hex_bytes_to_string(record.parentRecordId);
+        let trail: String = RecheckRecords::hex_bytes_to_string(record.trail);
+        let trail_signature: String = encode(&record.trailSignature);
+        let creator: AccountId = record.creator;
+        let timestamp: Timestamp = record.timestamp;
+        let mut sub_records_length: u64 = 0;
+
+        if !contract_self.objectSubRecords.get(record_id_hash).is_none() {
+            sub_records_length = contract_self.objectSubRecords.get(record_id_hash).unwrap().len();
+        }
+
+        return (record_id, parent_record_id, trail, trail_signature, creator, timestamp, sub_records_length);
+    }
+
+    fn record_view(contract_self: &RecheckRecords, record_id_hash: &CryptoHash) -> Option<RecordView> {
+        let record: ObjectRecord = RecheckRecords::get_record(contract_self, record_id_hash)?;
+        let sub_records_count: u64 = contract_self.objectSubRecords.get(record_id_hash)
+            .map(|sub_records| sub_records.len())
+            .unwrap_or(0);
+        let status: RecordStatus = RecheckRecords::current_status(&record);
+        let is_valid: bool = RecheckRecords::is_currently_valid(&record);
+
+        return Some(RecordView {
+            record_id: RecheckRecords::hex_bytes_to_string(record.recordId),
+            parent_record_id: RecheckRecords::hex_bytes_to_string(record.parentRecordId),
+            trail: RecheckRecords::hex_bytes_to_string(record.trail),
+            trail_signature: encode(&record.trailSignature),
+            signer_public_key: record.signerPublicKey,
+            creator: record.creator,
+            signer: record.signer,
+            timestamp: U64(record.timestamp),
+            sub_records_count: U64(sub_records_count),
+            extra0: RecheckRecords::hex_bytes_to_string(record.extra0),
+            extra1: RecheckRecords::hex_bytes_to_string(record.extra1),
+            extras_explicit: record.extrasExplicit,
+            status,
+            status_timestamp: record.statusTimestamp.map(U64),
+            status_reason: record.statusReason.map(RecheckRecords::hex_bytes_to_string),
+            valid_from: record.validFrom.map(U64),
+            valid_until: record.validUntil.map(U64),
+            is_valid,
+            author_public_key: record.authorPublicKey,
+        });
+    }
+
+    fn record_validity_view(record: &ObjectRecord) -> RecordValidityView {
+        return RecordValidityView {
+            record_id: RecheckRecords::hex_bytes_to_string(record.recordId),
+            status: RecheckRecords::current_status(record),
+            valid_from: record.validFrom.map(U64),
+            valid_until: record.validUntil.map(U64),
+            is_valid: RecheckRecords::is_currently_valid(record),
+        };
+    }
+
+    // Active records are reported as expired from their validUntil on, the stored status is kept
+    fn current_status(record: &ObjectRecord) -> RecordStatus {
+        return match record.validUntil {
+            Some(valid_until) if record.status == RecordStatus::Active && valid_until <= block_timestamp() => RecordStatus::Expired,
+            _ => record.status,
+        };
+    }
+
+    fn is_currently_valid(record: &ObjectRecord) -> bool {
+        let valid_from: Timestamp = record.validFrom.unwrap_or(0);
+
+        return RecheckRecords::current_status(record) == RecordStatus::Active && valid_from <= block_timestamp();
+    }
+
+    fn null_record() -> (String,
+                         String,
+                         String,
+                         String,
+                         AccountId,
+                         Timestamp,
+                         u64) {
+        let null_account_id: AccountId = "none.none".parse().unwrap();
+
+        return (
+            String::from("0"),
+            String::from("0"),
+            String::from("0"),
+            String::from("0"),
+            null_account_id,
+            0,
+            0
+        );
+    }
+}
+
+//Write functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    #[allow(non_snake_case)]
+    pub fn createSubRecordWithExtras2(&mut self,
+                                      record_id_str: String,
+                                      parent_record_id_str: String,
+                                      trail_str: String,
+                                      trail_signature_str: String,
+                                      extra_0_str: String,
+                                      extra_1_str: String) {
+        return RecheckRecords::create_record(self,
+                                             record_id_str,
+                                             parent_record_id_str,
+                                             trail_str,
+                                             trail_signature_str,
+                                             extra_0_str,
+                                             extra_1_str,
+                                             RecordOptions { extrasExplicit: true, ..Default::default() });
+    }
+
+    #[allow(non_snake_case)]
+    pub fn createSubRecordWithSignature(&mut self,
+                                        record_id_str: String,
+                                        parent_record_id_str: String,
+                                        trail_str: String,
+                                        trail_signature_str: String,
+                                        signer_public_key: PublicKey,
+                                        extra_0_str: String,
+                                        extra_1_str: String) {
+        return RecheckRecords::create_record(self,
+                                             record_id_str,
+                                             parent_record_id_str,
+                                             trail_str,
+                                             trail_signature_str,
+                                             extra_0_str,
+                                             extra_1_str,
+                                             RecordOptions {
+                                                 extrasExplicit: true,
+                                                 signerPublicKey: Some(signer_public_key),
+                                                 ..Default::default()
+                                             });
+    }
+
+    // valid_from and valid_until are nanosecond timestamps, a record without them is valid from
+    // its creation until it is revoked or superseded
+    #[allow(non_snake_case)]
+    pub fn createSubRecordWithValidity(&mut self,
+                                       record_id_str: String,
+                                       parent_record_id_str: String,
+                                       trail_str: String,
+                                       trail_signature_str: String,
+                                       extra_0_str: String,
+                                       extra_1_str: String,
+                                       valid_from: Option<U64>,
+                                       valid_until: Option<U64>) {
+        return RecheckRecords::create_record(self,
+                                             record_id_str,
+                                             parent_record_id_str,
+                                             trail_str,
+                                             trail_signature_str,
+                                             extra_0_str,
+                                             extra_1_str,
+                                             RecordOptions {
+                                                 extrasExplicit: true,
+                                                 validFrom: valid_from.map(u64::from),
+                                                 validUntil: valid_until.map(u64::from),
+                                                 ..Default::default()
+                                             });
+    }
+
+    // The record is anchored and paid for by the calling writer, with the public key that signed
+    // the payload stored as its author. Nonces of a key have to increase with every record.
+    #[allow(non_snake_case)]
+    pub fn createRecordSigned(&mut self,
+                              payload: SubRecordWithExtrasInput,
+                              public_key: PublicKey,
+                              signature: String,
+                              nonce: U64) {
+        RecheckRecords::only_writers(self);
+
+        let nonce: u64 = nonce.into();
+
+        require!(public_key.curve_type() == CurveType::ED25519, "Signed records require an ed25519 public key.");
+        require!(nonce > self.signedRecordNonces.get(&public_key).unwrap_or(0), "Nonce was already used.");
+
+        let message: CryptoHash = RecheckRecords::signed_record_message(&payload, nonce);
+        let signature: Vec<u8> = <Vec<u8>>::from_hex(&signature).expect("Invalid signature hex string.");
+        require!(RecheckRecords::verify_signature(&message, &signature, &public_key), "Invalid payload signature.");
+
+        let storage_usage_before: StorageUsage = env::storage_usage();
+        self.signedRecordNonces.insert(&public_key, &nonce);
+        let storage_used: StorageUsage = env::storage_usage().saturating_sub(storage_usage_before);
+        RecheckRecords::charge_storage(self, &predecessor_account_id(), storage_used);
+
+        return RecheckRecords::create_record(self,
+                                             payload.record_id_str,
+                                             payload.parent_record_id_str,
+                                             payload.trail_str,
+                                             payload.trail_signature_str,
+                                             payload.extra_0_str,
+                                             payload.extra_1_str,
+                                             RecordOptions {
+                                                 extrasExplicit: true,
+                                                 authorPublicKey: Some(public_key),
+                                                 ..Default::default()
+                                             });
+    }
+
+    #[allow(non_snake_case)]
+    pub fn createSubRecordsWithExtras2(&mut self, records: Vec<SubRecordWithExtrasInput>) -> Vec<BatchItemResult> {
+        return RecheckRecords::insert_records_batch(self, records, true);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn createRecords(&mut self, records: Vec<RecordInput>) -> Vec<BatchItemResult> {
+        let inputs: Vec<SubRecordWithExtrasInput> = records.into_iter()
+            .map(|record| SubRecordWithExtrasInput {
+                parent_record_id_str: record.record_id_str.clone(),
+                extra_0_str: record.trail_str.clone(),
+                extra_1_str: record.trail_str.clone(),
+                record_id_str: record.record_id_str,
+                trail_str: record.trail_str,
+                trail_signature_str: record.trail_signature_str,
+            })
+            .collect();
+
+        return RecheckRecords::insert_records_batch(self, inputs, false);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn createSubRecord(&mut self,
+                           record_id_str: String,
+                           parent_record_id_str: String,
+                           trail_str: String,
+                           trail_signature_str: String) {
+        RecheckRecords::only_unique_records(&self, &record_id_str);
+
+        let extra_0_str: String = trail_str.clone();
+        let extra_1_str: String = trail_str.clone();
+
+        return RecheckRecords::create_record(self,
+                                             record_id_str,
+                                             parent_record_id_str,
+                                             trail_str,
+                                             trail_signature_str,
+                                             extra_0_str,
+                                             extra_1_str,
+                                             RecordOptions::default());
+    }
+
+    #[allow(non_snake_case)]
+    pub fn createRecord(&mut self,
+                        record_id_str: String,
+                        trail_str: String,
+                        trail_signature_str: String) {
+        RecheckRecords::only_unique_records(&self, &record_id_str);
+
+        let parent_record_id_str: String = record_id_str.clone();
+        let extra_0_str: String = trail_str.clone();
+        let extra_1_str: String = trail_str.clone();
+
+        return RecheckRecords::create_record(self,
+                                             record_id_str,
+                                             parent_record_id_str,
+                                             trail_str,
+                                             trail_signature_str,
+                                             extra_0_str,
+                                             extra_1_str,
+                                             RecordOptions::default());
+    }
+}
+
+//Status functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    #[allow(non_snake_case)]
+    pub fn revokeRecord(&mut self, record_id_str: String, reason_hash_str: String) {
+        RecheckRecords::change_record_status(self, record_id_str, reason_hash_str, RecordStatus::Revoked);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn supersedeRecord(&mut self, record_id_str: String, reason_hash_str: String) {
+        RecheckRecords::change_record_status(self, record_id_str, reason_hash_str, RecordStatus::Superseded);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn recordStatus(&self, record_id_str: String) -> Option<RecordStatusView> {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+
+        return RecheckRecords::get_record(self, &record_id)
+            .map(|record| RecordStatusView {
+                status: RecheckRecords::current_status(&record),
+                status_timestamp: record.statusTimestamp.map(U64),
+                status_reason: record.statusReason.map(RecheckRecords::hex_bytes_to_string),
+            });
+    }
+}
+
+//Linking policy functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    #[allow(non_snake_case)]
+    pub fn setLinkPolicy(&mut self, record_id_str: String, policy: LinkPolicy) {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+        let caller: AccountId = predecessor_account_id();
+
+        let record: ObjectRecord = RecheckRecords::get_record(self, &record_id)
+            .expect("None existing record");
+        require!(record.creator == caller || caller == self.ownerId,
+            "Only the record creator or the owner can change the link policy.");
+
+        let storage_usage_before: StorageUsage = env::storage_usage();
+
+        if policy == LinkPolicy::CreatorOnly {
+            self.linkPolicies.remove(&record_id);
+        } else {
+            self.linkPolicies.insert(&record_id, &policy);
+        }
+
+        // Policies are stored for the record, its creator pays when the policy adds storage
+        let storage_used: StorageUsage = env::storage_usage().saturating_sub(storage_usage_before);
+        if storage_used > 0 {
+            RecheckRecords::charge_storage(self, &record.creator, storage_used);
+        }
+    }
+
+    // Allows an account to link sub records to the caller's records with the Allowlist policy
+    #[allow(non_snake_case)]
+    pub fn addToLinkAllowlist(&mut self, account_id: AccountId) {
+        let caller: AccountId = predecessor_account_id();
+        let storage_usage_before: StorageUsage = env::storage_usage();
+
+        let mut allowlist: UnorderedSet<AccountId> = self.linkAllowlists.get(&caller)
+            .unwrap_or_else(|| UnorderedSet::new(StorageKeys::LinkAllowlist { creator: caller.clone() }));
+        allowlist.insert(&account_id);
+        self.linkAllowlists.insert(&caller, &allowlist);
+
+        let storage_used: StorageUsage = env::storage_usage().saturating_sub(storage_usage_before);
+        RecheckRecords::charge_storage(self, &caller, storage_used);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn removeFromLinkAllowlist(&mut self, account_id: AccountId) {
+        let caller: AccountId = predecessor_account_id();
+
+        let mut allowlist: UnorderedSet<AccountId> = self.linkAllowlists.get(&caller)
+            .expect("Account not found in the link allowlist.");
+        require!(allowlist.remove(&account_id), "Account not found in the link allowlist.");
+        self.linkAllowlists.insert(&caller, &allowlist);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn linkPolicy(&self, record_id_str: String) -> Option<LinkPolicy> {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+
+        return RecheckRecords::get_record(self, &record_id)
+            .map(|_| RecheckRecords::link_policy(self, &record_id));
+    }
+
+    #[allow(non_snake_case)]
+    pub fn linkAllowlist(&self, creator: AccountId, from_index: u64, limit: u64) -> Vec<AccountId> {
+        return match self.linkAllowlists.get(&creator) {
+            Some(allowlist) => allowlist.iter()
+                .skip(from_index as usize)
+                .take(limit as usize)
+                .collect(),
+            None => vec![],
+        };
+    }
+
+    #[allow(non_snake_case)]
+    pub fn canLinkSubRecord(&self, parent_record_id_str: String, account_id: AccountId) -> bool {
+        let parent_record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(parent_record_id_str);
+
+        return RecheckRecords::get_record(self, &parent_record_id)
+            .map(|parent_record| RecheckRecords::can_link(self, &parent_record, &account_id))
+            .unwrap_or(false);
+    }
+}
+
+//Merkle anchoring functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    #[allow(non_snake_case)]
+    pub fn createMerkleRoot(&mut self, root_str: String, leaf_count: u64, metadata_str: String) {
+        RecheckRecords::only_writers(self);
+
+        let root: CryptoHash = RecheckRecords::string_to_hex_bytes(root_str);
+        let metadata: CryptoHash = RecheckRecords::string_to_hex_bytes(metadata_str);
+        require!(self.merkleRoots.get(&root).is_none(), "Merkle root must be unique.");
+        require!(leaf_count > 0, "Merkle tree must have at least one leaf.");
+
+        let storage_usage_before: StorageUsage = env::storage_usage();
+
+        let merkle_root = MerkleRoot {
+            root,
+            leafCount: leaf_count,
+            metadata,
+            creator: predecessor_account_id(),
+            timestamp: block_timestamp(),
+        };
+
+        RecheckRecords::emit_event("merkle_root_created", &[MerkleRootEventData {
+            root: RecheckRecords::hex_bytes_to_string(merkle_root.root),
+            leaf_count: U64(merkle_root.leafCount),
+            metadata: RecheckRecords::hex_bytes_to_string(merkle_root.metadata),
+            creator: merkle_root.creator.clone(),
+            timestamp: U64(merkle_root.timestamp),
+        }]);
+
+        self.merkleRoots.insert(&root, &merkle_root);
+
+        let storage_used: StorageUsage = env::storage_usage().saturating_sub(storage_usage_before);
+        RecheckRecords::charge_storage(self, &merkle_root.creator, storage_used);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn merkleRoot(&self, root_str: String) -> Option<MerkleRootView> {
+        let root: CryptoHash = RecheckRecords::string_to_hex_bytes(root_str);
+
+        return self.merkleRoots.get(&root)
+            .map(|merkle_root| MerkleRootView {
+                root: RecheckRecords::hex_bytes_to_string(merkle_root.root),
+                leaf_count: U64(merkle_root.leafCount),
+                metadata: RecheckRecords::hex_bytes_to_string(merkle_root.metadata),
+                creator: merkle_root.creator,
+                timestamp: U64(merkle_root.timestamp),
+            });
+    }
+
+    // Proof hashes are ordered from the leaf level up to the level below the root
+    #[allow(non_snake_case)]
+    pub fn verifyInclusion(&self, root_str: String, leaf_str: String, proof: Vec<String>, index: u64) -> bool {
+        let root: CryptoHash = RecheckRecords::string_to_hex_bytes(root_str);
+        let leaf: CryptoHash = RecheckRecords::string_to_hex_bytes(leaf_str);
+
+        let merkle_root: MerkleRoot = match self.merkleRoots.get(&root) {
+            Some(merkle_root) => merkle_root,
+            None => return false,
+        };
+
+        // Proofs must reach down to the leaf level so inner nodes can't be passed off as leaves
+        let tree_depth: u32 = u64::BITS - (merkle_root.leafCount - 1).leading_zeros();
+
+        if index >= merkle_root.leafCount || proof.len() != tree_depth as usize {
+            return false;
+        }
+
+        let proof: Vec<CryptoHash> = proof.into_iter()
+            .map(RecheckRecords::string_to_hex_bytes)
+            .collect();
+
+        return RecheckRecords::compute_merkle_root(leaf, &proof, index) == root;
+    }
+}
+
+//Migration functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    // Upgrades the stored contract structure to the current layout, safe to call on current state.
+    // Legacy records are not moved, they stay readable from the legacy map.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract: RecheckRecords = match RecheckRecords::read_state() {
+            VersionedRecheckRecords::V1(contract_v1) => Self {
+                objectRecords: UnorderedMap::new(StorageKeys::VersionedObjectRecordsMap),
+                legacyObjectRecords: contract_v1.objectRecords,
+                objectSubRecords: contract_v1.objectSubRecords,
+                trails: contract_v1.trails,
+                e0: contract_v1.e0,
+                e1: contract_v1.e1,
+                writers: UnorderedMap::new(StorageKeys::WritersMap),
+                storageDeposits: LookupMap::new(StorageKeys::StorageDepositsMap),
+                trailRecords: UnorderedMap::new(StorageKeys::TrailRecordsMap),
+                extra0Records: UnorderedMap::new(StorageKeys::Extra0RecordsMap),
+                extra1Records: UnorderedMap::new(StorageKeys::Extra1RecordsMap),
+                duplicateTrailPolicy: DuplicateTrailPolicy::Allow,
+                ownerId: current_account_id(),
+                proposedOwnerId: None,
+                paused: false,
+                stagedCode: LazyOption::new(StorageKeys::StagedCode, None),
+                stagedUpgrade: None,
+                upgradeTimelock: DEFAULT_UPGRADE_TIMELOCK,
+                merkleRoots: LookupMap::new(StorageKeys::MerkleRootsMap),
+                recordStats: RecordStats::default(),
+                creatorRecordCounts: UnorderedMap::new(StorageKeys::CreatorRecordCountsMap),
+                creatorRecords: UnorderedMap::new(StorageKeys::CreatorRecordsMap),
+                parentRecordRequired: true,
+                maxTreeDepth: DEFAULT_MAX_TREE_DEPTH,
+                linkPolicies: LookupMap::new(StorageKeys::LinkPoliciesMap),
+                linkAllowlists: LookupMap::new(StorageKeys::LinkAllowlistsMap),
+                signedRecordNonces: LookupMap::new(StorageKeys::SignedRecordNoncesMap),
+                sharedSubRecordsLength: 0,
+                sharedSubRecordsCleared: 0,
+                indexedLegacyRecords: LookupSet::new(StorageKeys::IndexedLegacyRecordsSet),
+                versionedLegacyRecords: 0,
+            },
+            VersionedRecheckRecords::V2(contract) => contract,
+        };
+
+        RecheckRecords::write_state_version(StateVersion::V2);
+
+        return contract;
+    }
+
+    fn index_page_gas_left() -> bool {
+        return env::used_gas() + INDEX_PAGE_GAS_RESERVE <= env::prepaid_gas();
+    }
+
+    // Drops the sub record lists and the creator counters in pages of up to limit storage removals
+    // before rebuildIndexes. Child lists created before per-parent prefixes share one storage
+    // prefix, their elements are removed once up to the longest list. Elements of per-parent lists
+    // are left in place, the rebuilt lists write over them. Returns the number of removals left.
+    #[allow(non_snake_case)]
+    pub fn clearIndexes(&mut self, limit: u64) -> u64 {
+        RecheckRecords::only_owner(self);
+        require!(self.paused, "Pause the contract before rebuilding indexes.");
+
+        let mut removals: u64 = 0;
+
+        while removals < limit && RecheckRecords::index_page_gas_left() && !self.objectSubRecords.is_empty() {
+            let parent_record_id: CryptoHash = self.objectSubRecords.keys_as_vector()
+                .get(self.objectSubRecords.len() - 1)
+                .expect("None existing sub record");
+            let sub_records: Vector<CryptoHash> = self.objectSubRecords.remove(&parent_record_id)
+                .expect("None existing sub record");
+
+            self.sharedSubRecordsLength = std::cmp::max(self.sharedSubRecordsLength, sub_records.len());
+            removals += 1;
+        }
+
+        let shared_prefix: Vec<u8> = StorageKeys::SubRecordsVector.into_storage_key();
+
+        while removals < limit && RecheckRecords::index_page_gas_left()
+            && self.sharedSubRecordsCleared < self.sharedSubRecordsLength {
+            let mut element_key: Vec<u8> = shared_prefix.clone();
+            element_key.extend_from_slice(&self.sharedSubRecordsCleared.to_le_bytes());
+            env::storage_remove(&element_key);
+
+            self.sharedSubRecordsCleared += 1;
+            removals += 1;
+        }
+
+        while removals < limit && RecheckRecords::index_page_gas_left() && !self.creatorRecordCounts.is_empty() {
+            let creator: AccountId = self.creatorRecordCounts.keys_as_vector()
+                .get(self.creatorRecordCounts.len() - 1)
+                .expect("None existing creator");
+            self.creatorRecordCounts.remove(&creator);
+
+            removals += 1;
+        }
+
+        return self.objectSubRecords.len()
+            + (self.sharedSubRecordsLength - self.sharedSubRecordsCleared)
+            + self.creatorRecordCounts.len();
+    }
+
+    // Rebuilds the sub records, trail and extras indexes and the record counters from the stored
+    // records in pages, after clearIndexes dropped the sub record lists and creator counters. The
+    // contract stays paused until the last page so new records aren't counted twice. A page ends
+    // early when gas runs low. Returns the index to continue from.
+    #[allow(non_snake_case)]
+    pub fn rebuildIndexes(&mut self, from_index: u64, limit: u64) -> u64 {
+        RecheckRecords::only_owner(self);
+        require!(self.paused, "Pause the contract before rebuilding indexes.");
+
+        if from_index == 0 {
+            require!(self.objectSubRecords.is_empty() && self.creatorRecordCounts.is_empty(),
+                "Clear the indexes with clearIndexes before rebuilding.");
+
+            self.recordStats = RecordStats::default();
+        }
+
+        let to_index: u64 = std::cmp::min(from_index.saturating_add(limit), RecheckRecords::records_length(self));
+        let legacy_records_length: u64 = self.legacyObjectRecords.len();
+
+        for index in from_index..to_index {
+            if index > from_index && !RecheckRecords::index_page_gas_left() {
+                return index;
+            }
+
+            let record: ObjectRecord = match RecheckRecords::get_record_at(self, index) {
+                Some(record) => record,
+                None => continue,
+            };
+
+            RecheckRecords::index_sub_record(self, &record);
+            RecheckRecords::count_record(self, &record);
+
+            // Only legacy records are missing from the trail, extras and creator indexes, each is added once
+            if index < legacy_records_length && self.indexedLegacyRecords.insert(&record.recordId) {
+                RecheckRecords::index_record(self, &record);
+                RecheckRecords::index_creator(self, &record);
+            }
+        }
+
+        return to_index;
+    }
+
+    #[allow(non_snake_case)]
+    pub fn setDuplicateTrailPolicy(&mut self, policy: DuplicateTrailPolicy) {
+        RecheckRecords::only_owner(self);
+        self.duplicateTrailPolicy = policy;
+    }
+
+    // Turned off while importing legacy data whose parents are anchored after their sub records
+    #[allow(non_snake_case)]
+    pub fn setParentRecordRequired(&mut self, required: bool) {
+        RecheckRecords::only_owner(self);
+        self.parentRecordRequired = required;
+    }
+
+    #[allow(non_snake_case)]
+    pub fn setMaxTreeDepth(&mut self, max_depth: u64) {
+        RecheckRecords::only_owner(self);
+        require!(max_depth > 0, "Maximum tree depth must be at least 1.");
+        self.maxTreeDepth = max_depth;
+    }
+}
+
+//Storage management functions (NEP-145)
+#[near_bindgen]
+impl RecheckRecords {
+    #[payable]
+    pub fn storage_deposit(&mut self,
+                           account_id: Option<AccountId>,
+                           registration_only: Option<bool>) -> StorageBalance {
+        let amount: Balance = env::attached_deposit();
+        let account_id: AccountId = account_id.unwrap_or_else(predecessor_account_id);
+        let registration_only: bool = registration_only.unwrap_or(false);
+        let min_balance: Balance = self.storage_balance_bounds().min.0;
+
+        let deposit: StorageDeposit = match self.storageDeposits.get(&account_id) {
+            Some(mut deposit) => {
+                if registration_only {
+                    if amount > 0 {
+                        Promise::new(predecessor_account_id()).transfer(amount);
+                    }
+                } else {
+                    deposit.total += amount;
+                }
+                deposit
+            }
+            None => {
+                require!(amount >= min_balance, "The attached deposit is less than the minimum storage balance.");
+
+                let mut total: Balance = amount;
+                if registration_only && amount > min_balance {
+                    Promise::new(predecessor_account_id()).transfer(amount - min_balance);
+                    total = min_balance;
+                }
+                StorageDeposit { total, usedBytes: 0 }
+            }
+        };
+
+        self.storageDeposits.insert(&account_id, &deposit);
+
+        return RecheckRecords::storage_balance(&deposit);
+    }
+
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        near_sdk::assert_one_yocto();
+        let account_id: AccountId = predecessor_account_id();
+
+        let mut deposit: StorageDeposit = self.storageDeposits.get(&account_id)
+            .expect("The account is not registered for storage.");
+        let available: Balance = RecheckRecords::storage_balance(&deposit).available.0;
+        let amount: Balance = amount.map(|amount| amount.0).unwrap_or(available);
+
+        require!(amount <= available, "The amount is greater than the available storage balance.");
+
+        if amount > 0 {
+            deposit.total -= amount;
+            self.storageDeposits.insert(&account_id, &deposit);
+            Promise::new(account_id).transfer(amount);
+        }
+
+        return RecheckRecords::storage_balance(&deposit);
+    }
+
+    // Accounts that still pay for records can't unregister, `force` is not supported
+    #[payable]
+    pub fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        near_sdk::assert_one_yocto();
+        require!(!force.unwrap_or(false), "Force unregister is not supported.");
+        let account_id: AccountId = predecessor_account_id();
+
+        let deposit: StorageDeposit = match self.storageDeposits.get(&account_id) {
+            Some(deposit) => deposit,
+            None => return false,
+        };
+        require!(deposit.usedBytes == 0, "Can't unregister an account that pays for records.");
+
+        self.storageDeposits.remove(&account_id);
+        Promise::new(account_id).transfer(deposit.total);
+
+        return true;
+    }
+
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        return StorageBalanceBounds {
+            min: U128(RecheckRecords::storage_cost(STORAGE_DEPOSIT_ENTRY_BYTES)),
+            max: None,
+        };
+    }
+
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        return self.storageDeposits.get(&account_id)
+            .map(|deposit| RecheckRecords::storage_balance(&deposit));
+    }
+}
+
+//Ownership functions
+#[near_bindgen]
+impl RecheckRecords {
+    // Deployments without an init call are owned by the contract account
+    #[init]
+    pub fn new(owner_id: AccountId) -> Self {
+        return Self {
+            ownerId: owner_id,
+            ..Default::default()
+        };
+    }
+
+    // Ownership is handed over in two steps so it can't be moved to an account nobody controls
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
+        RecheckRecords::only_owner(self);
+        self.proposedOwnerId = Some(new_owner_id);
+    }
+
+    pub fn accept_ownership(&mut self) {
+        let caller: AccountId = predecessor_account_id();
+
+        require!(self.proposedOwnerId.as_ref() == Some(&caller), "Only the proposed owner can accept ownership.");
+
+        self.ownerId = caller;
+        self.proposedOwnerId = None;
+    }
+
+    pub fn owner_id(&self) -> AccountId {
+        return self.ownerId.clone();
+    }
+
+    pub fn proposed_owner_id(&self) -> Option<AccountId> {
+        return self.proposedOwnerId.clone();
+    }
+
+    // Stops all record creation, records can still be read, revoked and superseded
+    pub fn pause(&mut self) {
+        RecheckRecords::only_owner_or_guardian(self);
+        self.paused = true;
+    }
+
+    pub fn unpause(&mut self) {
+        RecheckRecords::only_owner_or_guardian(self);
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        return self.paused;
+    }
+}
+
+//Upgrade functions
+#[near_bindgen]
+impl RecheckRecords {
+    pub fn stage_upgrade(&mut self, code: Base64VecU8) {
+        RecheckRecords::only_owner(self);
+
+        let code: Vec<u8> = code.into();
+        require!(!code.is_empty(), "Staged code must not be empty.");
+
+        self.stagedUpgrade = Some(StagedUpgrade {
+            codeHash: env::sha256_array(&code),
+            stagedAt: block_timestamp(),
+        });
+        self.stagedCode.set(&code);
+    }
+
+    // Deploys the staged code and migrates the state in the same receipt, a failing
+    // migration reverts the deployment
+    pub fn apply_upgrade(&mut self) -> Promise {
+        RecheckRecords::only_owner(self);
+
+        let staged_upgrade: StagedUpgrade = self.stagedUpgrade.take().expect("No upgrade is staged.");
+        require!(block_timestamp() >= staged_upgrade.stagedAt.saturating_add(self.upgradeTimelock),
+            "The upgrade timelock has not expired.");
+
+        let code: Vec<u8> = self.stagedCode.get().expect("No upgrade is staged.");
+        self.stagedCode.remove();
+
+        return Promise::new(current_account_id())
+            .deploy_contract(code)
+            .function_call(String::from("migrate"), b"{}".to_vec(), 0, MIGRATE_GAS);
+    }
+
+    pub fn cancel_upgrade(&mut self) {
+        RecheckRecords::only_owner(self);
+        require!(self.stagedUpgrade.take().is_some(), "No upgrade is staged.");
+
+        self.stagedCode.remove();
+    }
+
+    // The timelock can't be changed while an upgrade is waiting for it
+    pub fn set_upgrade_timelock(&mut self, timelock: U64) {
+        RecheckRecords::only_owner(self);
+        require!(self.stagedUpgrade.is_none(), "An upgrade is already staged.");
+        require!(timelock.0 >= MIN_UPGRADE_TIMELOCK, "Upgrade timelock must be at least one hour.");
+
+        self.upgradeTimelock = timelock.into();
+    }
+
+    pub fn upgrade_timelock(&self) -> U64 {
+        return U64(self.upgradeTimelock);
+    }
+
+    pub fn staged_upgrade(&self) -> Option<StagedUpgradeView> {
+        return self.stagedUpgrade.as_ref()
+            .map(|staged_upgrade| StagedUpgradeView {
+                code_hash: RecheckRecords::hex_bytes_to_string(staged_upgrade.codeHash),
+                staged_at: U64(staged_upgrade.stagedAt),
+                applicable_at: U64(staged_upgrade.stagedAt.saturating_add(self.upgradeTimelock)),
+            });
+    }
+}
+
+//Writers registry functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    #[allow(non_snake_case)]
+    pub fn addWriter(&mut self, account_id: AccountId, role: Role) {
+        RecheckRecords::only_owner(self);
+        self.writers.insert(&account_id, &role);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn removeWriter(&mut self, account_id: AccountId) {
+        RecheckRecords::only_owner(self);
+        require!(self.writers.remove(&account_id).is_some(), "Writer not found.");
+    }
+
+    #[allow(non_snake_case)]
+    pub fn writerRole(&self, account_id: AccountId) -> Option<Role> {
+        return self.writers.get(&account_id);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn writers(&self, from_index: u64, limit: u64) -> Vec<(AccountId, Role)> {
+        return self.writers.iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect();
+    }
+}
+
+//Read-only functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    #[allow(non_snake_case)]
+    pub fn records(self, record_id_str: String) -> (String,
+                                                    String,
+                                                    String,
+                                                    String,
+                                                    AccountId,
+                                                    Timestamp,
+                                                    u64) {
+        let record_id_hash: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+
+        return RecheckRecords::record_tuple(&self, &record_id_hash);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn subRecord(self, sub_record_id_str: String, index: u64) -> (String,
+                                                                      String,
+                                                                      String,
+                                                                      String,
+                                                                      AccountId,
+                                                                      Timestamp,
+                                                                      u64) {
+        let parent_record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(sub_record_id_str);
+
+        if self.objectSubRecords.get(&parent_record_id).is_none() {
+            return RecheckRecords::null_record();
+        }
+
+
+        let sub_records: Vector<CryptoHash> = self.objectSubRecords.get(&parent_record_id)
+            .expect("None existing sub record");
+
+        if sub_records.get(index).is_none() {
+            return RecheckRecords::null_record();
+        }
+
+        let indexed_sub_record_id: CryptoHash = sub_records.get(index)
+            .expect("None existing sub record");
+
+        return RecheckRecords::record_tuple(&self, &indexed_sub_record_id);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn subRecords(&self, parent_record_id_str: String, from_index: u64, limit: u64) -> Vec<RecordView> {
+        let parent_record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(parent_record_id_str);
+
+        if self.objectSubRecords.get(&parent_record_id).is_none() {
+            return vec![];
+        }
+
+        let sub_records: Vector<CryptoHash> = self.objectSubRecords.get(&parent_record_id)
+            .expect("None existing sub record");
+
+        return sub_records.iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .filter_map(|sub_record_id| RecheckRecords::record_view(self, &sub_record_id))
+            .collect();
+    }
+
+    #[allow(non_snake_case)]
+    pub fn verifyTrail(self, trail_str: String) -> (String,
+                                                    String,
+                                                    String,
+                                                    String,
+                                                    AccountId,
+                                                    Timestamp,
+                                                    u64) {
+        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
+
+        if self.trails.get(&trail).is_none() {
+            return RecheckRecords::null_record();
+        }
+
+
+        let record_id: CryptoHash = self.trails.get(&trail)
+            .expect("None existing record");
+
+        let record_id_str: String = RecheckRecords::hex_bytes_to_string(record_id);
+
+        return RecheckRecords::records(self, record_id_str);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn verifyExtra0(self, extra_0_str: String) -> (String,
+                                                       String,
+                                                       String,
+                                                       String,
+                                                       AccountId,
+                                                       Timestamp,
+                                                       u64) {
+        let extra_0: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_0_str);
+
+        if self.e0.get(&extra_0).is_none() {
+            return RecheckRecords::null_record();
+        }
+
+
+        let record_id: CryptoHash = self.e0.get(&extra_0)
+            .expect("None existing record");
+
+        let record_id_str: String = RecheckRecords::hex_bytes_to_string(record_id);
+
+        return RecheckRecords::records(self, record_id_str);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn verifyExtra1(self, extra_1_str: String) -> (String,
+                                                       String,
+                                                       String,
+                                                       String,
+                                                       AccountId,
+                                                       Timestamp,
+                                                       u64) {
+        let extra_1: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_1_str);
+
+        if self.e1.get(&extra_1).is_none() {
+            return RecheckRecords::null_record();
+        }
+
+        let record_id: CryptoHash = self.e1.get(&extra_1)
+            .expect("None existing record");
+
+        let record_id_str: String = RecheckRecords::hex_bytes_to_string(record_id);
+
+        return RecheckRecords::records(self, record_id_str);
+    }
+}
+
+//Read-only JSON functions
+#[near_bindgen]
+#[allow(non_snake_case)]
+impl RecheckRecords {
+    #[allow(non_snake_case)]
+    pub fn recordView(&self, record_id_str: String) -> Option<RecordView> {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+
+        return RecheckRecords::record_view(self, &record_id);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn subRecordView(&self, parent_record_id_str: String, index: u64) -> Option<RecordView> {
+        let parent_record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(parent_record_id_str);
+
+        return self.objectSubRecords.get(&parent_record_id)
+            .and_then(|sub_records| sub_records.get(index))
+            .and_then(|sub_record_id| RecheckRecords::record_view(self, &sub_record_id));
+    }
+
+    #[allow(non_snake_case)]
+    pub fn verifyTrailView(&self, trail_str: String) -> Option<RecordView> {
+        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
+
+        return self.trails.get(&trail)
+            .and_then(|record_id| RecheckRecords::record_view(self, &record_id));
+    }
+
+    #[allow(non_snake_case)]
+    pub fn verifyExtra0View(&self, extra_0_str: String) -> Option<RecordView> {
+        let extra_0: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_0_str);
+
+        return self.e0.get(&extra_0)
+            .and_then(|record_id| RecheckRecords::record_view(self, &record_id));
+    }
+
+    #[allow(non_snake_case)]
+    pub fn verifyExtra1View(&self, extra_1_str: String) -> Option<RecordView> {
+        let extra_1: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_1_str);
+
+        return self.e1.get(&extra_1)
+            .and_then(|record_id| RecheckRecords::record_view(self, &record_id));
+    }
+
+    #[allow(non_snake_case)]
+    pub fn recordsByTrail(&self, trail_str: String, from_index: u64, limit: u64) -> Vec<RecordView> {
+        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
+
+        return RecheckRecords::index_page(self, &self.trailRecords, &trail, from_index, limit);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn recordsByExtra0(&self, extra_0_str: String, from_index: u64, limit: u64) -> Vec<RecordView> {
+        let extra_0: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_0_str);
+
+        return RecheckRecords::index_page(self, &self.extra0Records, &extra_0, from_index, limit);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn recordsByExtra1(&self, extra_1_str: String, from_index: u64, limit: u64) -> Vec<RecordView> {
+        let extra_1: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_1_str);
+
+        return RecheckRecords::index_page(self, &self.extra1Records, &extra_1, from_index, limit);
+    }
+
+    #[allow(non_snake_case)]
+    pub fn recordsByCreator(&self, account_id: AccountId, from_index: u64, limit: u64) -> Vec<RecordView> {
+        return RecheckRecords::index_page(self, &self.creatorRecords, &account_id, from_index, limit);
+    }
+
+    // Parents are returned nearest first, ending at the root record, at a missing parent or at the
+    // first record seen twice. Parents don't have to exist when a record is created, so the chain
+    // is bounded by max_depth.
+    pub fn ancestors(&self, record_id_str: String, max_depth: u64) -> Vec<RecordView> {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+        let mut ancestors: Vec<RecordView> = vec![];
+        let mut visited: HashSet<CryptoHash> = HashSet::new();
+
+        let mut record: ObjectRecord = match RecheckRecords::get_record(self, &record_id) {
+            Some(record) => record,
+            None => return ancestors,
+        };
+
+        while (ancestors.len() as u64) < max_depth && record.recordId != record.parentRecordId {
+            record = match RecheckRecords::get_record(self, &record.parentRecordId) {
+                Some(parent_record) => parent_record,
+                None => break,
+            };
+
+            if !visited.insert(record.recordId) {
+                break;
+            }
+
+            if let Some(parent_view) = RecheckRecords::record_view(self, &record.recordId) {
+                ancestors.push(parent_view);
+            }
+        }
+
+        return ancestors;
+    }
+
+    // Sub records are returned breadth first, level by level down to max_depth
+    pub fn descendants(&self, record_id_str: String, max_depth: u64, limit: u64) -> Vec<TreeRecordView> {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+        let mut descendants: Vec<TreeRecordView> = vec![];
+        let mut visited: HashSet<CryptoHash> = HashSet::new();
+        let mut queue: VecDeque<(CryptoHash, u64)> = VecDeque::new();
+
+        visited.insert(record_id);
+        queue.push_back((record_id, 0));
+
+        while let Some((parent_record_id, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+
+            let sub_records: Vector<CryptoHash> = match self.objectSubRecords.get(&parent_record_id) {
+                Some(sub_records) => sub_records,
+                None => continue,
+            };
+
+            for sub_record_id in sub_records.iter() {
+                if descendants.len() as u64 >= limit {
+                    return descendants;
+                }
+
+                if !visited.insert(sub_record_id) {
+                    continue;
+                }
+
+                if let Some(record) = RecheckRecords::record_view(self, &sub_record_id) {
+                    descendants.push(TreeRecordView { depth: depth + 1, record });
+                    queue.push_back((sub_record_id, depth + 1));
+                }
+            }
+        }
+
+        return descendants;
+    }
+
+    #[allow(non_snake_case)]
+    pub fn parentRecordRequired(&self) -> bool {
+        return self.parentRecordRequired;
+    }
+
+    #[allow(non_snake_case)]
+    pub fn maxTreeDepth(&self) -> u64 {
+        return self.maxTreeDepth;
+    }
+
+    // Pages over all parents with sub records, so a page can hold fewer orphans than the limit.
+    // Continue from from_index + limit until it passes the number of parents.
+    pub fn orphans(&self, from_index: u64, limit: u64) -> Vec<OrphanView> {
+        return self.objectSubRecords.iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .filter(|(parent_record_id, _)| RecheckRecords::get_record(self, parent_record_id).is_none())
+            .map(|(parent_record_id, sub_records)| OrphanView {
+                parent_record_id: RecheckRecords::hex_bytes_to_string(parent_record_id),
+                sub_records_count: U64(sub_records.len()),
+            })
+            .collect();
+    }
+
+    #[allow(non_snake_case)]
+    pub fn duplicateTrailPolicy(&self) -> DuplicateTrailPolicy {
+        return self.duplicateTrailPolicy;
+    }
+
+    pub fn stats(&self) -> StatsView {
+        return StatsView {
+            total_records: U64(RecheckRecords::records_count(self)),
+            root_records: U64(self.recordStats.rootRecords),
+            sub_records: U64(self.recordStats.subRecords),
+            distinct_trails: U64(self.trailRecords.len()),
+            distinct_creators: U64(self.creatorRecordCounts.len()),
+        };
+    }
+
+    #[allow(non_snake_case)]
+    pub fn creatorStats(&self, from_index: u64, limit: u64) -> Vec<(AccountId, U64)> {
+        return self.creatorRecordCounts.iter()
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .map(|(account_id, records_count)| (account_id, U64(records_count)))
+            .collect();
+    }
+
+    // Last nonce used by the key, the next signed record needs a higher one
+    #[allow(non_snake_case)]
+    pub fn signedRecordNonce(&self, public_key: PublicKey) -> U64 {
+        return U64(self.signedRecordNonces.get(&public_key).unwrap_or(0));
+    }
+
+    // Checks the trail signature of the record found by trail against its signer public key
+    #[allow(non_snake_case)]
+    pub fn verifyTrailSignature(&self, trail_str: String) -> bool {
+        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
+
+        let record: ObjectRecord = match self.trails.get(&trail)
+            .and_then(|record_id| RecheckRecords::get_record(self, &record_id)) {
+            Some(record) => record,
+            None => return false,
+        };
+
+        return match &record.signerPublicKey {
+            Some(public_key) => RecheckRecords::verify_signature(&record.trail, &record.trailSignature, public_key),
+            None => false,
+        };
+    }
+
+    #[allow(non_snake_case)]
+    pub fn recordValidity(&self, record_id_str: String) -> Option<RecordValidityView> {
+        let record_id: CryptoHash = RecheckRecords::string_to_hex_bytes(record_id_str);
+
+        return RecheckRecords::get_record(self, &record_id)
+            .map(|record| RecheckRecords::record_validity_view(&record));
+    }
+
+    #[allow(non_snake_case)]
+    pub fn verifyTrailValidity(&self, trail_str: String) -> Option<RecordValidityView> {
+        let trail: CryptoHash = RecheckRecords::string_to_hex_bytes(trail_str);
+
+        return self.trails.get(&trail)
+            .and_then(|record_id| RecheckRecords::get_record(self, &record_id))
+            .map(|record| RecheckRecords::record_validity_view(&record));
+    }
+}
+
+// 4. Tests
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::{log, testing_env};
+
+    const ONE_NEAR: Balance = 1_000_000_000_000_000_000_000_000;
+    // Total length of all logs a single receipt can write
+    const MAX_TOTAL_LOG_LENGTH: usize = 16384;
+
+    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder.current_account_id(predecessor_account_id.clone())
+            .signer_account_id(predecessor_account_id.clone())
+            .predecessor_account_id(predecessor_account_id);
+
+        return builder;
+    }
+
+    fn sign_hash_string(key_type: near_crypto::KeyType, hash_str: &String) -> (PublicKey, String) {
+        let secret_key = near_crypto::SecretKey::from_random(key_type);
+        let hash_bytes: CryptoHash = RecheckRecords::string_to_hex_bytes(hash_str.clone());
+
+        let signature_bytes: Vec<u8> = match secret_key.sign(&hash_bytes) {
+            near_crypto::Signature::ED25519(signature) => signature.to_bytes().to_vec(),
+            near_crypto::Signature::SECP256K1(signature) => <[u8; 65]>::from(signature).to_vec(),
+        };
+        let public_key: PublicKey = secret_key.public_key().to_string().parse().unwrap();
+
+        return (public_key, encode(signature_bytes));
+    }
+
+    fn create_random_hash_string() -> String {
+        use rand::Rng;
+
+        const HEX_CHARSET: &[u8] = b"abcdef0123456789";
+        const HASH_LEN: usize = 64;
+        let mut rng = rand::thread_rng();
+
+        let random_hash: String = (0..HASH_LEN)
+            .map(|_| {
+                let idx = rng.gen_range(0..HEX_CHARSET.len());
+                HEX_CHARSET[idx] as char
+            })
+            .collect();
+
+        return random_hash;
+    }
+
+    #[test]
+    fn create_and_get_new_record() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_str{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              random_trail_signature.clone());
+
+        let result = contract.records(random_record_id.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_and_get_new_sub_record() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_parent_record_id = create_random_hash_string();
+        log!("random_parent_record_id{:?}",random_parent_record_id);
+        let random_parent_trail = create_random_hash_string();
+        log!("random_parent_trail{:?}",random_parent_trail);
+        let random_parent_trail_signature = create_random_hash_string();
+        log!("random_parent_trail_signature{:?}",random_parent_record_id);
+
+        contract.createRecord(random_parent_record_id.clone(),
+                              random_parent_trail.clone(),
+                              random_parent_trail_signature.clone());
+
+
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+        let random_sub_trail = create_random_hash_string();
+        log!("random_sub_trail{:?}",random_sub_trail);
+        let random_sub_trail_signature = create_random_hash_string();
+        log!("random_trail_str{:?}",random_parent_record_id);
+
+        contract.createSubRecord(random_sub_record_id.clone(),
+                                 random_parent_record_id.clone(),
+                                 random_sub_trail.clone(),
+                                 random_sub_trail_signature.clone());
+
+        let result = contract.subRecord(random_parent_record_id.clone(), 0);
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_sub_record_id.clone()),
+            String::from(random_parent_record_id.clone()),
+            String::from(random_sub_trail.clone()),
+            String::from(random_sub_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn get_non_existing_sub_record_index() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_parent_record_id = create_random_hash_string();
+        log!("random_parent_record_id{:?}",random_parent_record_id);
+
+        contract.createRecord(random_parent_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createSubRecord(create_random_hash_string(),
+                                 random_parent_record_id.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+
+        let result = contract.subRecord(random_parent_record_id.clone(), 1);
+
+        log!("result{:?}",result);
+
+        let expected = RecheckRecords::null_record();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_and_get_sub_records_page() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_parent_record_id = create_random_hash_string();
+        log!("random_parent_record_id{:?}",random_parent_record_id);
+
+        contract.createRecord(random_parent_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        let mut random_sub_record_ids: Vec<String> = vec![];
+        for _ in 0..3 {
+            let random_sub_record_id = create_random_hash_string();
+            log!("random_sub_record_id{:?}",random_sub_record_id);
+
+            contract.createSubRecord(random_sub_record_id.clone(),
+                                     random_parent_record_id.clone(),
+                                     create_random_hash_string(),
+                                     create_random_hash_string());
+            random_sub_record_ids.push(random_sub_record_id);
+        }
+
+        let result = contract.subRecords(random_parent_record_id.clone(), 1, 5);
+
+        log!("result{:?}",result);
+
+        let result_ids: Vec<String> = result.into_iter().map(|record| record.record_id).collect();
+
+        assert_eq!(result_ids, random_sub_record_ids[1..].to_vec());
+        assert!(contract.subRecords(create_random_hash_string(), 0, 5).is_empty());
+    }
+
+    #[test]
+    fn create_and_get_new_record_with_extras_2() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_parent_trail_signature{:?}",random_trail_signature);
+        let random_extra_0 = create_random_hash_string();
+        log!("random_extra_0{:?}",random_extra_0);
+        let random_extra_1 = create_random_hash_string();
+        log!("random_extra_1{:?}",random_extra_1);
+
+        contract.createSubRecordWithExtras2(random_record_id.clone(),
+                                            random_record_id.clone(),
+                                            random_trail.clone(),
+                                            random_trail_signature.clone(),
+                                            random_extra_0.clone(),
+                                            random_extra_1.clone());
+
+        let result = contract.records(random_record_id.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_and_verify_trail() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_str{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              random_trail_signature.clone());
+
+        let result = contract.verifyTrail(random_trail.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_and_verify_extra_0() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_parent_trail_signature{:?}",random_trail_signature);
+        let random_extra_0 = create_random_hash_string();
+        log!("random_extra_0{:?}",random_extra_0);
+        let random_extra_1 = create_random_hash_string();
+        log!("random_extra_1{:?}",random_extra_1);
+
+        contract.createSubRecordWithExtras2(random_record_id.clone(),
+                                            random_record_id.clone(),
+                                            random_trail.clone(),
+                                            random_trail_signature.clone(),
+                                            random_extra_0.clone(),
+                                            random_extra_1.clone());
+
+        let result = contract.verifyExtra0(random_extra_0.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_and_verify_extra_1() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_parent_trail_signature{:?}",random_trail_signature);
+        let random_extra_0 = create_random_hash_string();
+        log!("random_extra_0{:?}",random_extra_0);
+        let random_extra_1 = create_random_hash_string();
+        log!("random_extra_1{:?}",random_extra_1);
+
+        contract.createSubRecordWithExtras2(random_record_id.clone(),
+                                            random_record_id.clone(),
+                                            random_trail.clone(),
+                                            random_trail_signature.clone(),
+                                            random_extra_0.clone(),
+                                            random_extra_1.clone());
+
+        let result = contract.verifyExtra1(random_extra_1.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Record must be unique.")]
+    fn create_non_unique_record() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_str{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              random_trail_signature.clone());
+
+        let random_trail_2 = create_random_hash_string();
+        log!("random_trail_2{:?}",random_trail_2);
+        let random_trail_signature_2 = create_random_hash_string();
+        log!("random_trail_signature_2{:?}",random_trail_signature_2);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail_2.clone(),
+                              random_trail_signature_2.clone());
+    }
+
+    #[test]
+    fn get_non_existing_record() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let contract = RecheckRecords::default();
+        testing_env!(context.is_view(true).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        let result = contract.records(random_record_id);
+        log!("result{:?}",result);
+
+        let expected = RecheckRecords::null_record();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn verify_wrong_trail() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_str{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              random_trail_signature.clone());
+
+        let result = contract.verifyTrail(random_trail_signature.clone());
+
+        log!("result{:?}",result);
+
+        let expected = RecheckRecords::null_record();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn verify_wrong_extra_0() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_parent_trail_signature{:?}",random_trail_signature);
+        let random_extra_0 = create_random_hash_string();
+        log!("random_extra_0{:?}",random_extra_0);
+        let random_extra_1 = create_random_hash_string();
+        log!("random_extra_1{:?}",random_extra_1);
+
+        contract.createSubRecordWithExtras2(random_record_id.clone(),
+                                            random_record_id.clone(),
+                                            random_trail.clone(),
+                                            random_trail_signature.clone(),
+                                            random_extra_0.clone(),
+                                            random_extra_1.clone());
+
+        let result = contract.verifyExtra0(random_extra_1.clone());
+
+        log!("result{:?}",result);
+
+        let expected = RecheckRecords::null_record();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn verify_wrong_extra_1() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_parent_trail_signature{:?}",random_trail_signature);
+        let random_extra_0 = create_random_hash_string();
+        log!("random_extra_0{:?}",random_extra_0);
+        let random_extra_1 = create_random_hash_string();
+        log!("random_extra_1{:?}",random_extra_1);
+
+        contract.createSubRecordWithExtras2(random_record_id.clone(),
+                                            random_record_id.clone(),
+                                            random_trail.clone(),
+                                            random_trail_signature.clone(),
+                                            random_extra_0.clone(),
+                                            random_extra_1.clone());
+
+        let result = contract.verifyExtra1(random_extra_0.clone());
+
+        log!("result{:?}",result);
+
+        let expected = RecheckRecords::null_record();
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_sub_records_for_different_parents() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_parent_record_id_1 = create_random_hash_string();
+        log!("random_parent_record_id_1{:?}",random_parent_record_id_1);
+        let random_parent_record_id_2 = create_random_hash_string();
+        log!("random_parent_record_id_2{:?}",random_parent_record_id_2);
+        let random_sub_record_id_1 = create_random_hash_string();
+        log!("random_sub_record_id_1{:?}",random_sub_record_id_1);
+        let random_sub_record_id_2 = create_random_hash_string();
+        log!("random_sub_record_id_2{:?}",random_sub_record_id_2);
+
+        contract.createRecord(random_parent_record_id_1.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createRecord(random_parent_record_id_2.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createSubRecord(random_sub_record_id_1.clone(),
+                                 random_parent_record_id_1.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+        contract.createSubRecord(random_sub_record_id_2.clone(),
+                                 random_parent_record_id_2.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+
+        let sub_records_1: Vec<CryptoHash> = contract.objectSubRecords
+            .get(&RecheckRecords::string_to_hex_bytes(random_parent_record_id_1))
+            .unwrap()
+            .to_vec();
+        let sub_records_2: Vec<CryptoHash> = contract.objectSubRecords
+            .get(&RecheckRecords::string_to_hex_bytes(random_parent_record_id_2))
+            .unwrap()
+            .to_vec();
+
+        assert_eq!(sub_records_1, vec![RecheckRecords::string_to_hex_bytes(random_sub_record_id_1)]);
+        assert_eq!(sub_records_2, vec![RecheckRecords::string_to_hex_bytes(random_sub_record_id_2)]);
+    }
+
+    #[test]
+    fn rebuild_shared_prefix_sub_records() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_parent_record_id_1 = create_random_hash_string();
+        log!("random_parent_record_id_1{:?}",random_parent_record_id_1);
+        let random_parent_record_id_2 = create_random_hash_string();
+        log!("random_parent_record_id_2{:?}",random_parent_record_id_2);
+        let random_sub_record_id_1 = create_random_hash_string();
+        log!("random_sub_record_id_1{:?}",random_sub_record_id_1);
+        let random_sub_record_id_2 = create_random_hash_string();
+        log!("random_sub_record_id_2{:?}",random_sub_record_id_2);
+
+        contract.createRecord(random_parent_record_id_1.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createRecord(random_parent_record_id_2.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createSubRecord(random_sub_record_id_1.clone(),
+                                 random_parent_record_id_1.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+        contract.createSubRecord(random_sub_record_id_2.clone(),
+                                 random_parent_record_id_2.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+
+        // Recreate the child lists the way they were stored before per-parent prefixes
+        let parent_record_id_1: CryptoHash = RecheckRecords::string_to_hex_bytes(random_parent_record_id_1);
+        let parent_record_id_2: CryptoHash = RecheckRecords::string_to_hex_bytes(random_parent_record_id_2);
+        contract.objectSubRecords.clear();
+        for (parent_record_id, sub_record_id) in [(parent_record_id_1, &random_sub_record_id_1),
+            (parent_record_id_2, &random_sub_record_id_2)] {
+            let mut legacy_sub_records: Vector<CryptoHash> = Vector::new(StorageKeys::SubRecordsVector);
+            legacy_sub_records.push(&RecheckRecords::string_to_hex_bytes(sub_record_id.clone()));
+            contract.objectSubRecords.insert(&parent_record_id, &legacy_sub_records);
+        }
+
+        let corrupted: CryptoHash = contract.objectSubRecords.get(&parent_record_id_1).unwrap().get(0).unwrap();
+        assert_eq!(corrupted, RecheckRecords::string_to_hex_bytes(random_sub_record_id_2.clone()));
+
+        contract.pause();
+        assert_eq!(contract.clearIndexes(1), 3);
+        assert_eq!(contract.clearIndexes(1), 2);
+        assert_eq!(contract.clearIndexes(100), 0);
+        assert!(contract.objectSubRecords.is_empty());
+        assert!(contract.creatorRecordCounts.is_empty());
+
+        let next_index: u64 = contract.rebuildIndexes(0, 2);
+        assert_eq!(next_index, 2);
+        let next_index: u64 = contract.rebuildIndexes(next_index, 100);
+        assert_eq!(next_index, 4);
+
+        let sub_records_1: Vec<CryptoHash> = contract.objectSubRecords.get(&parent_record_id_1).unwrap().to_vec();
+        let sub_records_2: Vec<CryptoHash> = contract.objectSubRecords.get(&parent_record_id_2).unwrap().to_vec();
+
+        assert_eq!(sub_records_1, vec![RecheckRecords::string_to_hex_bytes(random_sub_record_id_1)]);
+        assert_eq!(sub_records_2, vec![RecheckRecords::string_to_hex_bytes(random_sub_record_id_2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pause the contract before rebuilding indexes.")]
+    fn rebuild_indexes_while_unpaused() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.rebuildIndexes(0, 100);
+    }
+
+    #[test]
+    fn rebuild_indexes_stops_when_gas_runs_low() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        for _ in 0..3 {
+            contract.createRecord(create_random_hash_string(),
+                                  create_random_hash_string(),
+                                  create_random_hash_string());
+        }
+
+        contract.pause();
+        contract.clearIndexes(100);
+
+        testing_env!(context.prepaid_gas(INDEX_PAGE_GAS_RESERVE).build());
+
+        assert_eq!(contract.rebuildIndexes(0, 100), 1);
+
+        testing_env!(context.prepaid_gas(Gas(300_000_000_000_000)).build());
+
+        assert_eq!(contract.rebuildIndexes(1, 100), 3);
+        assert_eq!(contract.stats().root_records, U64(3));
+    }
+
+    fn create_v1_state(record_id: CryptoHash,
+                       sub_record_id: CryptoHash,
+                       trail: CryptoHash,
+                       trail_signature: CryptoHash) {
+        let mut contract_v1 = RecheckRecordsV1 {
+            objectRecords: UnorderedMap::new(StorageKeys::ObjectRecordsMap),
+            objectSubRecords: UnorderedMap::new(StorageKeys::ObjectSubRecordsMap),
+            trails: UnorderedMap::new(StorageKeys::TrailsMap),
+            e0: UnorderedMap::new(StorageKeys::E0map),
+            e1: UnorderedMap::new(StorageKeys::E1map),
+        };
+
+        for (id, parent_id) in [(record_id, record_id), (sub_record_id, record_id)] {
+            let record = ObjectRecordV1 {
+                recordId: id,
+                parentRecordId: parent_id,
+                trail,
+                trailSignature: trail_signature,
+                creator: accounts(1),
+                timestamp: 0,
+                extra0: trail,
+                extra1: trail,
+            };
+            contract_v1.objectRecords.insert(&id, &record);
+        }
+
+        let mut legacy_sub_records: Vector<CryptoHash> = Vector::new(StorageKeys::SubRecordsVector);
+        legacy_sub_records.push(&sub_record_id);
+        contract_v1.objectSubRecords.insert(&record_id, &legacy_sub_records);
+        contract_v1.trails.insert(&trail, &sub_record_id);
+
+        env::state_write(&contract_v1);
+    }
+
+    #[test]
+    fn migrate_v1_state() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_signature{:?}",random_trail_signature);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(random_sub_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(random_trail.clone()),
+                        RecheckRecords::string_to_hex_bytes(random_trail_signature.clone()));
+
+        assert_eq!(env::storage_read(STATE_VERSION_KEY), None);
+
+        let mut contract = RecheckRecords::migrate();
+
+        let state_version: Vec<u8> = env::storage_read(STATE_VERSION_KEY).unwrap();
+        assert!(matches!(StateVersion::try_from_slice(&state_version).unwrap(), StateVersion::V2));
+
+        contract.pause();
+        contract.clearIndexes(100);
+        contract.rebuildIndexes(0, 100);
+        contract.unpause();
+
+        let result = RecheckRecords::record_tuple(&contract, &RecheckRecords::string_to_hex_bytes(random_record_id.clone()));
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            1
+        );
+
+        assert_eq!(result, expected);
+
+        let random_new_record_id = create_random_hash_string();
+        log!("random_new_record_id{:?}",random_new_record_id);
+
+        contract.createSubRecord(random_new_record_id.clone(),
+                                 random_record_id.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+
+        let result_ids: Vec<String> = contract.subRecords(random_record_id.clone(), 0, 10)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        assert_eq!(result_ids, vec![random_sub_record_id.clone(), random_new_record_id.clone()]);
+
+        contract.pause();
+        contract.clearIndexes(100);
+        contract.rebuildIndexes(0, 100);
+
+        let result_ids: Vec<String> = contract.recordsByTrail(random_trail.clone(), 0, 10)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        assert_eq!(result_ids, vec![random_record_id.clone(), random_sub_record_id.clone()]);
+
+        let result = contract.verifyTrail(random_trail.clone());
+
+        log!("result{:?}",result);
+
+        assert_eq!(result.0, random_sub_record_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Record must be unique.")]
+    fn create_record_existing_in_v1_state() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
+
+        let mut contract = RecheckRecords::migrate();
+
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+    }
+
+    #[test]
+    fn migrate_current_state() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_signature{:?}",random_trail_signature);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              random_trail_signature.clone());
+        contract.addWriter(accounts(2), Role::Writer);
+
+        env::state_write(&contract);
+
+        let contract = RecheckRecords::migrate();
+
+        assert_eq!(contract.writerRole(accounts(2)), Some(Role::Writer));
+
+        let result = contract.records(random_record_id.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_sub_record_emits_events() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(42).build());
+
+        let random_parent_record_id = create_random_hash_string();
+        let random_sub_record_id = create_random_hash_string();
+        let random_sub_trail = create_random_hash_string();
+
+        contract.createRecord(random_parent_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createSubRecord(random_sub_record_id.clone(),
+                                 random_parent_record_id.clone(),
+                                 random_sub_trail.clone(),
+                                 create_random_hash_string());
+
+        let events: Vec<near_sdk::serde_json::Value> = get_logs().iter()
+            .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+            .map(|event| near_sdk::serde_json::from_str(event).unwrap())
+            .collect();
+
+        log!("events{:?}",events);
+
+        let event_names: Vec<&str> = events.iter()
+            .map(|event| event["event"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(event_names, vec!["record_created", "trail_indexed",
+                                     "record_created", "subrecord_linked", "trail_indexed"]);
+
+        let expected = json!({
+            "standard": "recheck",
+            "version": "1.0.0",
+            "event": "subrecord_linked",
+            "data": [{
+                "record_id": random_sub_record_id,
+                "parent_record_id": random_parent_record_id,
+                "trail": random_sub_trail,
+                "creator": accounts(1),
+                "timestamp": "42",
+            }],
+        });
+
+        assert_eq!(events[3], expected);
+    }
+
+    #[test]
+    fn create_and_get_record_views() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(42).build());
+
+        let random_parent_record_id = create_random_hash_string();
+        log!("random_parent_record_id{:?}",random_parent_record_id);
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_signature{:?}",random_trail_signature);
+        let random_extra_0 = create_random_hash_string();
+        log!("random_extra_0{:?}",random_extra_0);
+        let random_extra_1 = create_random_hash_string();
+        log!("random_extra_1{:?}",random_extra_1);
+
+        contract.createRecord(random_parent_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createSubRecordWithExtras2(random_record_id.clone(),
+                                            random_parent_record_id.clone(),
+                                            random_trail.clone(),
+                                            random_trail_signature.clone(),
+                                            random_extra_0.clone(),
+                                            random_extra_1.clone());
+
+        let result = contract.recordView(random_record_id.clone());
+
+        log!("result{:?}",result);
+
+        let expected = RecordView {
+            record_id: random_record_id.clone(),
+            parent_record_id: random_parent_record_id.clone(),
+            trail: random_trail.clone(),
+            trail_signature: random_trail_signature.clone(),
+            signer_public_key: None,
+            creator: accounts(1),
+            signer: accounts(1),
+            timestamp: U64(42),
+            sub_records_count: U64(0),
+            extra0: random_extra_0.clone(),
+            extra1: random_extra_1.clone(),
+            extras_explicit: true,
+            status: RecordStatus::Active,
+            status_timestamp: None,
+            status_reason: None,
+            valid_from: None,
+            valid_until: None,
+            is_valid: true,
+            author_public_key: None,
+        };
+
+        assert_eq!(result, Some(expected));
+        assert_eq!(contract.subRecordView(random_parent_record_id.clone(), 0), result);
+        assert_eq!(contract.verifyTrailView(random_trail.clone()), result);
+        assert_eq!(contract.verifyExtra0View(random_extra_0.clone()), result);
+        assert_eq!(contract.verifyExtra1View(random_extra_1.clone()), result);
+        assert_eq!(contract.recordView(random_parent_record_id.clone()).unwrap().sub_records_count, U64(1));
+    }
+
+    #[test]
+    fn create_record_with_default_extras() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              create_random_hash_string());
+
+        let result = contract.verifyExtra1View(random_trail.clone()).unwrap();
+
+        log!("result{:?}",result);
+
+        assert_eq!(result.record_id, random_record_id);
+        assert_eq!(result.extra0, random_trail);
+        assert_eq!(result.extra1, random_trail);
+        assert!(!result.extras_explicit);
+    }
+
+    #[test]
+    fn get_v1_record_view_extras() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(random_trail.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
+
+        let contract = RecheckRecords::migrate();
+
+        let result = contract.recordView(random_record_id.clone()).unwrap();
+
+        log!("result{:?}",result);
+
+        assert_eq!(result.extra0, random_trail);
+        assert_eq!(result.extra1, random_trail);
+        assert!(!result.extras_explicit);
+    }
+
+    #[test]
+    fn create_and_verify_trail_signature() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        for key_type in [near_crypto::KeyType::ED25519, near_crypto::KeyType::SECP256K1] {
+            let random_record_id = create_random_hash_string();
+            log!("random_record_id{:?}",random_record_id);
+            let random_trail = create_random_hash_string();
+            log!("random_trail{:?}",random_trail);
+            let (public_key, trail_signature) = sign_hash_string(key_type, &random_trail);
+            log!("trail_signature{:?}",trail_signature);
+
+            contract.createSubRecordWithSignature(random_record_id.clone(),
+                                                  random_record_id.clone(),
+                                                  random_trail.clone(),
+                                                  trail_signature.clone(),
+                                                  public_key.clone(),
+                                                  random_trail.clone(),
+                                                  random_trail.clone());
+
+            let result = contract.recordView(random_record_id.clone()).unwrap();
+
+            log!("result{:?}",result);
+
+            assert_eq!(result.trail_signature, trail_signature);
+            assert_eq!(result.signer_public_key, Some(public_key));
+            assert!(contract.verifyTrailSignature(random_trail.clone()));
+        }
+    }
+
+    #[test]
+    fn verify_wrong_trail_signature() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let (public_key, _) = sign_hash_string(near_crypto::KeyType::ED25519, &random_trail);
+        let (_, other_trail_signature) = sign_hash_string(near_crypto::KeyType::ED25519, &random_trail);
+
+        contract.createSubRecordWithSignature(random_record_id.clone(),
+                                              random_record_id.clone(),
+                                              random_trail.clone(),
+                                              other_trail_signature,
+                                              public_key,
+                                              random_trail.clone(),
+                                              random_trail.clone());
+
+        assert!(!contract.verifyTrailSignature(random_trail.clone()));
+        assert!(!contract.verifyTrailSignature(create_random_hash_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Trail signature doesn't match the public key curve.")]
+    fn create_record_with_signature_for_other_curve() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        let random_trail = create_random_hash_string();
+        let (public_key, _) = sign_hash_string(near_crypto::KeyType::SECP256K1, &random_trail);
+        let (_, trail_signature) = sign_hash_string(near_crypto::KeyType::ED25519, &random_trail);
+
+        contract.createSubRecordWithSignature(random_record_id.clone(),
+                                              random_record_id.clone(),
+                                              random_trail.clone(),
+                                              trail_signature,
+                                              public_key,
+                                              random_trail.clone(),
+                                              random_trail.clone());
+    }
+
+    #[test]
+    fn get_non_existing_record_view() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let contract = RecheckRecords::default();
+        testing_env!(context.is_view(true).build());
+
+        let result = contract.recordView(create_random_hash_string());
+
+        log!("result{:?}",result);
+
+        assert_eq!(result, None);
+        assert_eq!(contract.subRecordView(create_random_hash_string(), 0), None);
+        assert_eq!(contract.verifyTrailView(create_random_hash_string()), None);
+        assert_eq!(contract.verifyExtra0View(create_random_hash_string()), None);
+        assert_eq!(contract.verifyExtra1View(create_random_hash_string()), None);
+
+        let json_result: String = near_sdk::serde_json::to_string(&result).unwrap();
+        assert_eq!(json_result, "null");
+    }
+
+    #[test]
+    fn create_records_with_same_trail() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id_1 = create_random_hash_string();
+        log!("random_record_id_1{:?}",random_record_id_1);
+        let random_record_id_2 = create_random_hash_string();
+        log!("random_record_id_2{:?}",random_record_id_2);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_extra_1 = create_random_hash_string();
+        log!("random_extra_1{:?}",random_extra_1);
+
+        contract.createRecord(random_record_id_1.clone(),
+                              random_trail.clone(),
+                              create_random_hash_string());
+        contract.createSubRecordWithExtras2(random_record_id_2.clone(),
+                                            random_record_id_2.clone(),
+                                            random_trail.clone(),
+                                            create_random_hash_string(),
+                                            random_trail.clone(),
+                                            random_extra_1.clone());
+
+        let result_ids: Vec<String> = contract.recordsByTrail(random_trail.clone(), 0, 10)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        assert_eq!(result_ids, vec![random_record_id_1.clone(), random_record_id_2.clone()]);
+        assert_eq!(contract.recordsByTrail(random_trail.clone(), 1, 10).len(), 1);
+        assert_eq!(contract.recordsByExtra0(random_trail.clone(), 0, 10).len(), 2);
+        assert_eq!(contract.recordsByExtra1(random_trail.clone(), 0, 10).len(), 1);
+        assert_eq!(contract.recordsByExtra1(random_extra_1.clone(), 0, 10).len(), 1);
+        assert_eq!(contract.verifyTrail(random_trail.clone()).0, random_record_id_2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Trail must be unique.")]
+    fn create_records_with_same_trail_rejected() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.setDuplicateTrailPolicy(DuplicateTrailPolicy::Reject);
+        assert_eq!(contract.duplicateTrailPolicy(), DuplicateTrailPolicy::Reject);
+
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+
+        let records: Vec<RecordInput> = (0..2)
+            .map(|_| RecordInput {
+                record_id_str: create_random_hash_string(),
+                trail_str: random_trail.clone(),
+                trail_signature_str: create_random_hash_string(),
+            })
+            .collect();
+
+        let results = contract.createRecords(records);
+
+        log!("results{:?}",results);
+
+        assert!(results[0].created);
+        assert_eq!(results[1].error, Some(String::from("Trail must be unique.")));
+
+        contract.createRecord(create_random_hash_string(),
+                              random_trail.clone(),
+                              create_random_hash_string());
+    }
+
+    #[test]
+    fn create_records_batch() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_existing_record_id = create_random_hash_string();
+        log!("random_existing_record_id{:?}",random_existing_record_id);
+
+        contract.createRecord(random_existing_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_signature{:?}",random_trail_signature);
+
+        let record_ids: Vec<String> = vec![random_record_id.clone(),
+                                           random_existing_record_id.clone(),
+                                           random_record_id.clone(),
+                                           String::from("not-a-hash")];
+
+        let records: Vec<RecordInput> = record_ids.iter()
+            .map(|record_id| RecordInput {
+                record_id_str: record_id.clone(),
+                trail_str: random_trail.clone(),
+                trail_signature_str: random_trail_signature.clone(),
+            })
+            .collect();
+
+        let results = contract.createRecords(records);
+
+        log!("results{:?}",results);
+
+        let expected = vec![
+            BatchItemResult { record_id_str: random_record_id.clone(), created: true, error: None },
+            BatchItemResult { record_id_str: random_existing_record_id.clone(), created: false, error: Some(String::from("Record must be unique.")) },
+            BatchItemResult { record_id_str: random_record_id.clone(), created: false, error: Some(String::from("Record must be unique.")) },
+            BatchItemResult { record_id_str: String::from("not-a-hash"), created: false, error: Some(String::from("Invalid recordId hash.")) },
+        ];
+
+        assert_eq!(results, expected);
+
+        let result = contract.verifyTrail(random_trail.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(1),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn create_sub_records_with_extras_2_batch() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_parent_record_id = create_random_hash_string();
+        log!("random_parent_record_id{:?}",random_parent_record_id);
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+        let random_extra_0 = create_random_hash_string();
+        log!("random_extra_0{:?}",random_extra_0);
+
+        let records = vec![
+            SubRecordWithExtrasInput {
+                record_id_str: random_parent_record_id.clone(),
+                parent_record_id_str: random_parent_record_id.clone(),
+                trail_str: create_random_hash_string(),
+                trail_signature_str: create_random_hash_string(),
+                extra_0_str: create_random_hash_string(),
+                extra_1_str: create_random_hash_string(),
+            },
+            SubRecordWithExtrasInput {
+                record_id_str: random_sub_record_id.clone(),
+                parent_record_id_str: random_parent_record_id.clone(),
+                trail_str: create_random_hash_string(),
+                trail_signature_str: create_random_hash_string(),
+                extra_0_str: random_extra_0.clone(),
+                extra_1_str: String::from("0x"),
+            },
+        ];
+
+        let results = contract.createSubRecordsWithExtras2(records);
+
+        log!("results{:?}",results);
+
+        assert!(results[0].created);
+        assert_eq!(results[1].error, Some(String::from("Invalid extra1 hash.")));
+        assert_eq!(contract.subRecords(random_parent_record_id.clone(), 0, 10).len(), 0);
+        assert_eq!(contract.verifyExtra0(random_extra_0.clone()), RecheckRecords::null_record());
+    }
+
+    #[test]
+    fn create_records_batch_log_length() {
+        // Longest valid account id, so the events are as long as they get
+        let contract_account: AccountId = "a".repeat(64).parse().unwrap();
+        let mut context = get_context(contract_account);
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(u64::MAX).build());
+
+        let random_parent_record_id = create_random_hash_string();
+        log!("random_parent_record_id{:?}",random_parent_record_id);
+
+        contract.createRecord(random_parent_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        let records: Vec<SubRecordWithExtrasInput> = (0..MAX_BATCH_SIZE)
+            .map(|_| SubRecordWithExtrasInput {
+                record_id_str: create_random_hash_string(),
+                parent_record_id_str: random_parent_record_id.clone(),
+                trail_str: create_random_hash_string(),
+                trail_signature_str: create_random_hash_string(),
+                extra_0_str: create_random_hash_string(),
+                extra_1_str: create_random_hash_string(),
+            })
+            .collect();
+
+        testing_env!(context.build());
+
+        let results = contract.createSubRecordsWithExtras2(records);
+
+        assert!(results.iter().all(|result| result.created));
+
+        let logs_length: usize = get_logs().iter().map(|log| log.len()).sum();
+
+        log!("logs_length{:?}",logs_length);
+
+        assert!(logs_length <= MAX_TOTAL_LOG_LENGTH);
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch must contain between 1 and 15 records.")]
+    fn create_records_batch_too_large() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let records: Vec<RecordInput> = (0..MAX_BATCH_SIZE + 1)
+            .map(|_| RecordInput {
+                record_id_str: create_random_hash_string(),
+                trail_str: create_random_hash_string(),
+                trail_signature_str: create_random_hash_string(),
+            })
+            .collect();
+
+        contract.createRecords(records);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient storage deposit.")]
+    fn create_records_batch_with_insufficient_storage_deposit() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, Some(true));
+
+        testing_env!(context.attached_deposit(0).build());
+
+        let records: Vec<RecordInput> = (0..2)
+            .map(|_| RecordInput {
+                record_id_str: create_random_hash_string(),
+                trail_str: create_random_hash_string(),
+                trail_signature_str: create_random_hash_string(),
+            })
+            .collect();
+
+        contract.createRecords(records);
+    }
+
+    #[test]
+    fn add_writer_and_create_record() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, None);
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_trail_signature = create_random_hash_string();
+        log!("random_trail_signature{:?}",random_trail_signature);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              random_trail_signature.clone());
+
+        let result = contract.records(random_record_id.clone());
+
+        log!("result{:?}",result);
+
+        let expected = (
+            String::from(random_record_id.clone()),
+            String::from(random_record_id.clone()),
+            String::from(random_trail.clone()),
+            String::from(random_trail_signature.clone()),
+            accounts(2),
+            0,
+            0
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only authorized writers can create records.")]
+    fn create_record_by_auditor() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Auditor);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .build());
+
+        contract.createRecord(create_random_hash_string(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only authorized writers can create records.")]
+    fn create_record_by_removed_writer() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+        contract.addWriter(accounts(3), Role::Admin);
+        contract.removeWriter(accounts(2));
+
+        let result = contract.writers(0, 10);
+        log!("result{:?}",result);
+
+        assert_eq!(result, vec![(accounts(3), Role::Admin)]);
+        assert_eq!(contract.writerRole(accounts(2)), None);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .build());
+
+        contract.createRecord(create_random_hash_string(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+    }
+
+    #[test]
+    fn create_record_charges_storage_deposit() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        let balance = contract.storage_deposit(None, None);
+        let min_balance: Balance = contract.storage_balance_bounds().min.0;
+
+        assert_eq!(balance, StorageBalance { total: U128(ONE_NEAR), available: U128(ONE_NEAR - min_balance) });
+
+        testing_env!(context.attached_deposit(0).build());
+
+        let storage_usage_before: StorageUsage = env::storage_usage();
+
+        contract.createRecord(create_random_hash_string(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        let storage_used: StorageUsage = env::storage_usage() - storage_usage_before;
+        let balance = contract.storage_balance_of(accounts(2)).unwrap();
+
+        log!("balance{:?}",balance);
+
+        let expected = StorageBalance {
+            total: U128(ONE_NEAR),
+            available: U128(ONE_NEAR - min_balance - Balance::from(storage_used) * env::storage_byte_cost()),
+        };
+
+        assert_eq!(balance, expected);
+
+        testing_env!(context.attached_deposit(1).build());
+
+        let balance = contract.storage_withdraw(None);
+
+        assert_eq!(balance.available, U128(0));
+        assert_eq!(balance.total.0, min_balance + Balance::from(storage_used) * env::storage_byte_cost());
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient storage deposit.")]
+    fn create_record_with_insufficient_storage_deposit() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+
+        let min_balance: Balance = contract.storage_balance_bounds().min.0;
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        let balance = contract.storage_deposit(None, Some(true));
+
+        assert_eq!(balance, StorageBalance { total: U128(min_balance), available: U128(0) });
+
+        testing_env!(context.attached_deposit(0).build());
+
+        contract.createRecord(create_random_hash_string(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+    }
+
+    #[test]
+    fn create_and_revoke_record() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(42).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+        let random_reason_hash = create_random_hash_string();
+        log!("random_reason_hash{:?}",random_reason_hash);
+
+        contract.createRecord(random_record_id.clone(),
+                              random_trail.clone(),
+                              create_random_hash_string());
+
+        assert_eq!(contract.recordStatus(random_record_id.clone()).unwrap().status, RecordStatus::Active);
+
+        testing_env!(context.block_timestamp(43).build());
+
+        contract.revokeRecord(random_record_id.clone(), random_reason_hash.clone());
+
+        let result = contract.recordStatus(random_record_id.clone());
+
+        log!("result{:?}",result);
+
+        let expected = RecordStatusView {
+            status: RecordStatus::Revoked,
+            status_timestamp: Some(U64(43)),
+            status_reason: Some(random_reason_hash.clone()),
+        };
+
+        assert_eq!(result, Some(expected));
+
+        let result = contract.verifyTrailView(random_trail.clone()).unwrap();
+
+        assert_eq!(result.status, RecordStatus::Revoked);
+        assert_eq!(result.status_timestamp, Some(U64(43)));
+        assert_eq!(contract.recordStatus(create_random_hash_string()), None);
+    }
+
+    #[test]
+    fn supersede_and_revoke_record_by_admin() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+        contract.addWriter(accounts(3), Role::Admin);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, None);
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        let available_before: U128 = contract.storage_balance_of(accounts(2)).unwrap().available;
+
+        // The admin isn't registered for storage, the record creator pays for the status
+        testing_env!(context.predecessor_account_id(accounts(3))
+            .signer_account_id(accounts(3))
+            .attached_deposit(0)
+            .build());
+
+        contract.supersedeRecord(random_record_id.clone(), create_random_hash_string());
+
+        assert_eq!(contract.recordStatus(random_record_id.clone()).unwrap().status, RecordStatus::Superseded);
+
+        contract.revokeRecord(random_record_id.clone(), create_random_hash_string());
+
+        assert_eq!(contract.recordStatus(random_record_id.clone()).unwrap().status, RecordStatus::Revoked);
+        assert!(contract.storage_balance_of(accounts(2)).unwrap().available.0 < available_before.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the record creator or an admin can change the record status.")]
+    fn revoke_record_by_other_writer() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.addWriter(accounts(2), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .build());
+
+        contract.revokeRecord(random_record_id.clone(), create_random_hash_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Record is already revoked.")]
+    fn revoke_revoked_v1_record() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
+
+        let mut contract = RecheckRecords::migrate();
+
+        contract.revokeRecord(random_record_id.clone(), create_random_hash_string());
+
+        assert_eq!(contract.legacyObjectRecords.len(), 2);
+        assert_eq!(contract.recordStatus(random_record_id.clone()).unwrap().status, RecordStatus::Revoked);
+
+        contract.revokeRecord(random_record_id.clone(), create_random_hash_string());
+    }
+
+    #[test]
+    fn create_record_with_validity() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(100).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_trail = create_random_hash_string();
+        log!("random_trail{:?}",random_trail);
+
+        contract.createSubRecordWithValidity(random_record_id.clone(),
+                                             random_record_id.clone(),
+                                             random_trail.clone(),
+                                             create_random_hash_string(),
+                                             random_trail.clone(),
+                                             random_trail.clone(),
+                                             Some(U64(200)),
+                                             Some(U64(300)));
+
+        let result = contract.verifyTrailValidity(random_trail.clone());
+
+        log!("result{:?}",result);
+
+        let expected = RecordValidityView {
+            record_id: random_record_id.clone(),
+            status: RecordStatus::Active,
+            valid_from: Some(U64(200)),
+            valid_until: Some(U64(300)),
+            is_valid: false,
+        };
+
+        assert_eq!(result, Some(expected));
+
+        testing_env!(context.block_timestamp(200).build());
+
+        assert!(contract.verifyTrailValidity(random_trail.clone()).unwrap().is_valid);
+        assert!(contract.verifyTrailView(random_trail.clone()).unwrap().is_valid);
+
+        testing_env!(context.block_timestamp(300).build());
+
+        let result = contract.recordValidity(random_record_id.clone()).unwrap();
+
+        log!("result{:?}",result);
+
+        assert_eq!(result.status, RecordStatus::Expired);
+        assert!(!result.is_valid);
+        assert_eq!(contract.recordStatus(random_record_id.clone()).unwrap().status, RecordStatus::Expired);
+        assert_eq!(contract.verifyTrailValidity(create_random_hash_string()), None);
+    }
+
+    #[test]
+    fn revoked_record_is_not_valid() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        contract.createSubRecordWithValidity(random_record_id.clone(),
+                                             random_record_id.clone(),
+                                             create_random_hash_string(),
+                                             create_random_hash_string(),
+                                             create_random_hash_string(),
+                                             create_random_hash_string(),
+                                             None,
+                                             Some(U64(300)));
+
+        assert!(contract.recordValidity(random_record_id.clone()).unwrap().is_valid);
+
+        contract.revokeRecord(random_record_id.clone(), create_random_hash_string());
+
+        let result = contract.recordValidity(random_record_id.clone()).unwrap();
+
+        log!("result{:?}",result);
+
+        assert_eq!(result.status, RecordStatus::Revoked);
+        assert!(!result.is_valid);
+    }
+
+    #[test]
+    #[should_panic(expected = "validFrom must be before validUntil.")]
+    fn create_record_with_empty_validity() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        contract.createSubRecordWithValidity(random_record_id.clone(),
+                                             random_record_id.clone(),
+                                             create_random_hash_string(),
+                                             create_random_hash_string(),
+                                             create_random_hash_string(),
+                                             create_random_hash_string(),
+                                             Some(U64(300)),
+                                             Some(U64(300)));
+    }
+
+    #[test]
+    fn transfer_ownership() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::new(accounts(2));
+        testing_env!(context.is_view(false).build());
+
+        assert_eq!(contract.owner_id(), accounts(2));
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+
+        contract.addWriter(accounts(4), Role::Writer);
+        contract.propose_owner(accounts(3));
+
+        assert_eq!(contract.owner_id(), accounts(2));
+        assert_eq!(contract.proposed_owner_id(), Some(accounts(3)));
+
+        testing_env!(context.predecessor_account_id(accounts(3)).build());
+
+        contract.accept_ownership();
+
+        assert_eq!(contract.owner_id(), accounts(3));
+        assert_eq!(contract.proposed_owner_id(), None);
+
+        contract.removeWriter(accounts(4));
+
+        assert_eq!(contract.writerRole(accounts(4)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method.")]
+    fn add_writer_by_previous_owner() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        assert_eq!(contract.owner_id(), accounts(1));
+
+        contract.propose_owner(accounts(3));
+
+        testing_env!(context.predecessor_account_id(accounts(3)).build());
+
+        contract.accept_ownership();
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+
+        contract.addWriter(accounts(4), Role::Writer);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the proposed owner can accept ownership.")]
+    fn accept_ownership_without_proposal() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.propose_owner(accounts(3));
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+
+        contract.accept_ownership();
+    }
+
+    #[test]
+    #[should_panic(expected = "The contract is paused.")]
+    fn create_record_while_paused() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.addWriter(accounts(2), Role::Guardian);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+
+        contract.pause();
+
+        assert!(contract.is_paused());
+        assert!(contract.recordView(random_record_id.clone()).is_some());
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+
+        contract.createRecord(create_random_hash_string(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+    }
+
+    #[test]
+    fn create_records_after_unpause() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.pause();
+
+        assert!(contract.is_paused());
+
+        contract.unpause();
+
+        assert!(!contract.is_paused());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        assert!(contract.recordView(random_record_id.clone()).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner or a guardian can call this method.")]
+    fn pause_by_writer() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+
+        contract.pause();
+    }
+
+    #[test]
+    fn stage_and_apply_upgrade() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(100).build());
+
+        let code: Vec<u8> = vec![0, 97, 115, 109];
+
+        contract.set_upgrade_timelock(U64(MIN_UPGRADE_TIMELOCK));
+        contract.stage_upgrade(Base64VecU8::from(code.clone()));
+
+        let result = contract.staged_upgrade();
+
+        log!("result{:?}",result);
+
+        let expected = StagedUpgradeView {
+            code_hash: encode(env::sha256(&code)),
+            staged_at: U64(100),
+            applicable_at: U64(100 + MIN_UPGRADE_TIMELOCK),
+        };
+
+        assert_eq!(result, Some(expected));
+
+        testing_env!(context.block_timestamp(100 + MIN_UPGRADE_TIMELOCK).build());
+
+        contract.apply_upgrade();
+
+        assert_eq!(contract.staged_upgrade(), None);
+        assert_eq!(contract.stagedCode.get(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The upgrade timelock has not expired.")]
+    fn apply_upgrade_before_timelock() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(100).build());
 
-        if self.e0.get(&extra_0).is_none() {
-            return RecheckRecords::null_record();
-        }
+        contract.stage_upgrade(Base64VecU8::from(vec![0, 97, 115, 109]));
 
+        assert_eq!(contract.upgrade_timelock(), U64(DEFAULT_UPGRADE_TIMELOCK));
 
-        let record_id: CryptoHash = self.e0.get(&extra_0)
-            .expect("None existing record");
+        testing_env!(context.block_timestamp(99 + DEFAULT_UPGRADE_TIMELOCK).build());
 
-        let record_id_str: String = RecheckRecords::hex_bytes_to_string(record_id);
+        contract.apply_upgrade();
+    }
 
-        return RecheckRecords::records(self, record_id_str);
+    #[test]
+    #[should_panic(expected = "No upgrade is staged.")]
+    fn apply_cancelled_upgrade() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.stage_upgrade(Base64VecU8::from(vec![0, 97, 115, 109]));
+        contract.cancel_upgrade();
+
+        assert_eq!(contract.staged_upgrade(), None);
+
+        testing_env!(context.block_timestamp(DEFAULT_UPGRADE_TIMELOCK).build());
+
+        contract.apply_upgrade();
     }
 
-    #[allow(non_snake_case)]
-    pub fn verifyExtra1(self, extra_1_str: String) -> (String,
-                                                       String,
-                                                       String,
-                                                       String,
-                                                       AccountId,
-                                                       Timestamp,
-                                                       u64) {
-        let extra_1: CryptoHash = RecheckRecords::string_to_hex_bytes(extra_1_str);
+    #[test]
+    #[should_panic(expected = "Upgrade timelock must be at least one hour.")]
+    fn set_upgrade_timelock_below_minimum() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
 
-        if self.e1.get(&extra_1).is_none() {
-            return RecheckRecords::null_record();
-        }
+        contract.stage_upgrade(Base64VecU8::from(vec![0, 97, 115, 109]));
+        contract.cancel_upgrade();
 
-        let record_id: CryptoHash = self.e1.get(&extra_1)
-            .expect("None existing record");
+        contract.set_upgrade_timelock(U64(0));
+    }
 
-        let record_id_str: String = RecheckRecords::hex_bytes_to_string(record_id);
+    fn hash_pair(left: &String, right: &String) -> String {
+        let mut pair: Vec<u8> = RecheckRecords::string_to_hex_bytes(left.clone()).to_vec();
+        pair.extend_from_slice(&RecheckRecords::string_to_hex_bytes(right.clone()));
 
-        return RecheckRecords::records(self, record_id_str);
+        return encode(env::sha256(&pair));
     }
-}
 
-// 4. Tests
-#[cfg(all(test, not(target_arch = "wasm32")))]
-mod tests {
-    use super::*;
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::{log, testing_env};
+    #[test]
+    fn create_merkle_root_and_verify_inclusion() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).block_timestamp(42).build());
 
-    fn get_context(predecessor_account_id: AccountId) -> VMContextBuilder {
-        let mut builder = VMContextBuilder::new();
-        builder.current_account_id(predecessor_account_id.clone())
-            .signer_account_id(predecessor_account_id.clone())
-            .predecessor_account_id(predecessor_account_id);
+        let leaves: Vec<String> = (0..4).map(|_| create_random_hash_string()).collect();
+        log!("leaves{:?}",leaves);
+        let left_node = hash_pair(&leaves[0], &leaves[1]);
+        let right_node = hash_pair(&leaves[2], &leaves[3]);
+        let root = hash_pair(&left_node, &right_node);
+        log!("root{:?}",root);
+        let random_metadata = create_random_hash_string();
+        log!("random_metadata{:?}",random_metadata);
 
-        return builder;
+        contract.createMerkleRoot(root.clone(), 4, random_metadata.clone());
+
+        let result = contract.merkleRoot(root.clone());
+
+        log!("result{:?}",result);
+
+        let expected = MerkleRootView {
+            root: root.clone(),
+            leaf_count: U64(4),
+            metadata: random_metadata.clone(),
+            creator: accounts(1),
+            timestamp: U64(42),
+        };
+
+        assert_eq!(result, Some(expected));
+
+        assert!(contract.verifyInclusion(root.clone(), leaves[0].clone(), vec![leaves[1].clone(), right_node.clone()], 0));
+        assert!(contract.verifyInclusion(root.clone(), leaves[1].clone(), vec![leaves[0].clone(), right_node.clone()], 1));
+        assert!(contract.verifyInclusion(root.clone(), leaves[2].clone(), vec![leaves[3].clone(), left_node.clone()], 2));
+        assert!(contract.verifyInclusion(root.clone(), leaves[3].clone(), vec![leaves[2].clone(), left_node.clone()], 3));
+
+        assert!(!contract.verifyInclusion(root.clone(), leaves[0].clone(), vec![leaves[1].clone(), right_node.clone()], 1));
+        assert!(!contract.verifyInclusion(root.clone(), leaves[0].clone(), vec![leaves[1].clone(), right_node.clone()], 4));
+        assert!(!contract.verifyInclusion(root.clone(), left_node.clone(), vec![right_node.clone()], 0));
+        assert!(!contract.verifyInclusion(create_random_hash_string(), leaves[0].clone(), vec![leaves[1].clone(), right_node.clone()], 0));
+        assert_eq!(contract.merkleRoot(create_random_hash_string()), None);
     }
 
-    fn create_random_hash_string() -> String {
-        use rand::Rng;
+    #[test]
+    #[should_panic(expected = "Merkle root must be unique.")]
+    fn create_existing_merkle_root() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
 
-        const HEX_CHARSET: &[u8] = b"abcdef0123456789";
-        const HASH_LEN: usize = 64;
-        let mut rng = rand::thread_rng();
+        let random_root = create_random_hash_string();
+        log!("random_root{:?}",random_root);
 
-        let random_hash: String = (0..HASH_LEN)
-            .map(|_| {
-                let idx = rng.gen_range(0..HEX_CHARSET.len());
-                HEX_CHARSET[idx] as char
-            })
-            .collect();
+        contract.createMerkleRoot(random_root.clone(), 1, create_random_hash_string());
 
-        return random_hash;
+        assert!(contract.verifyInclusion(random_root.clone(), random_root.clone(), vec![], 0));
+
+        contract.createMerkleRoot(random_root.clone(), 1, create_random_hash_string());
     }
 
     #[test]
-    fn create_and_get_new_record() {
+    fn count_created_records() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
@@ -369,32 +4452,420 @@ mod tests {
         log!("random_record_id{:?}",random_record_id);
         let random_trail = create_random_hash_string();
         log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_trail_str{:?}",random_record_id);
 
         contract.createRecord(random_record_id.clone(),
                               random_trail.clone(),
-                              random_trail_signature.clone());
+                              create_random_hash_string());
+        contract.createSubRecordWithExtras2(create_random_hash_string(),
+                                            random_record_id.clone(),
+                                            random_trail.clone(),
+                                            create_random_hash_string(),
+                                            create_random_hash_string(),
+                                            create_random_hash_string());
+        contract.addWriter(accounts(2), Role::Writer);
+        contract.setLinkPolicy(random_record_id.clone(), LinkPolicy::Open);
 
-        let result = contract.records(random_record_id.clone());
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, None);
+        contract.createSubRecord(create_random_hash_string(),
+                                 random_record_id.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+
+        let result = contract.stats();
 
         log!("result{:?}",result);
 
-        let expected = (
-            String::from(random_record_id.clone()),
-            String::from(random_record_id.clone()),
-            String::from(random_trail.clone()),
-            String::from(random_trail_signature.clone()),
-            accounts(1),
-            0,
-            0
-        );
+        let expected = StatsView {
+            total_records: U64(3),
+            root_records: U64(1),
+            sub_records: U64(2),
+            distinct_trails: U64(2),
+            distinct_creators: U64(2),
+        };
+
+        assert_eq!(result, expected);
+        assert_eq!(contract.creatorStats(0, 10), vec![(accounts(1), U64(2)), (accounts(2), U64(1))]);
+    }
+
+    #[test]
+    fn count_v1_records_on_rebuild() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
+
+        let mut contract = RecheckRecords::migrate();
+
+        assert_eq!(contract.stats().root_records, U64(0));
+
+        contract.createRecord(create_random_hash_string(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        contract.pause();
+        contract.clearIndexes(100);
+        let next_index: u64 = contract.rebuildIndexes(0, 2);
+        contract.rebuildIndexes(next_index, 100);
+
+        let result = contract.stats();
+
+        log!("result{:?}",result);
+
+        let expected = StatsView {
+            total_records: U64(3),
+            root_records: U64(2),
+            sub_records: U64(1),
+            distinct_trails: U64(2),
+            distinct_creators: U64(1),
+        };
+
+        assert_eq!(result, expected);
+        assert_eq!(contract.creatorStats(0, 10), vec![(accounts(1), U64(3))]);
+    }
+
+    #[test]
+    fn revoke_v1_record_during_rebuild() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(random_sub_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
+
+        let mut contract = RecheckRecords::migrate();
+
+        contract.pause();
+        contract.clearIndexes(100);
+        let next_index: u64 = contract.rebuildIndexes(0, 1);
+
+        contract.revokeRecord(random_record_id.clone(), create_random_hash_string());
+
+        contract.rebuildIndexes(next_index, 100);
+
+        let result = contract.stats();
+
+        log!("result{:?}",result);
+
+        assert_eq!(result.total_records, U64(2));
+        assert_eq!(result.root_records, U64(1));
+        assert_eq!(result.sub_records, U64(1));
+
+        let result_ids: Vec<String> = contract.subRecords(random_record_id.clone(), 0, 10)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        assert_eq!(result_ids, vec![random_sub_record_id]);
+        assert_eq!(contract.recordStatus(random_record_id).unwrap().status, RecordStatus::Revoked);
+    }
+
+    #[test]
+    fn get_records_by_creator() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(random_sub_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
+
+        let mut contract = RecheckRecords::migrate();
+
+        let random_new_record_id = create_random_hash_string();
+        log!("random_new_record_id{:?}",random_new_record_id);
+
+        contract.createRecord(random_new_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+
+        assert_eq!(contract.recordsByCreator(accounts(1), 0, 10).len(), 1);
+
+        contract.pause();
+        contract.clearIndexes(100);
+        contract.rebuildIndexes(0, 100);
+        contract.clearIndexes(100);
+        contract.rebuildIndexes(0, 100);
+
+        let mut result_ids: Vec<String> = contract.recordsByCreator(accounts(1), 0, 10)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        log!("result_ids{:?}",result_ids);
+
+        assert_eq!(result_ids.remove(0), random_new_record_id);
+        result_ids.sort();
+
+        let mut expected_ids: Vec<String> = vec![random_record_id.clone(), random_sub_record_id.clone()];
+        expected_ids.sort();
+
+        assert_eq!(result_ids, expected_ids);
+        assert_eq!(contract.recordsByCreator(accounts(1), 2, 10).len(), 1);
+        assert_eq!(contract.recordsByCreator(accounts(2), 0, 10), vec![]);
+    }
+
+    #[test]
+    fn get_records_by_creator_with_revoked_v1_records() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id = create_random_hash_string();
+        log!("random_record_id{:?}",random_record_id);
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(random_sub_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
+
+        let mut contract = RecheckRecords::migrate();
+
+        contract.revokeRecord(random_record_id.clone(), create_random_hash_string());
+
+        contract.pause();
+        contract.clearIndexes(100);
+        let next_index: u64 = contract.rebuildIndexes(0, 1);
+
+        contract.revokeRecord(random_sub_record_id.clone(), create_random_hash_string());
+
+        contract.rebuildIndexes(next_index, 100);
+
+        let mut result_ids: Vec<String> = contract.recordsByCreator(accounts(1), 0, 10)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        log!("result_ids{:?}",result_ids);
+
+        result_ids.sort();
+
+        let mut expected_ids: Vec<String> = vec![random_record_id, random_sub_record_id];
+        expected_ids.sort();
+
+        assert_eq!(result_ids, expected_ids);
+        assert_eq!(contract.creatorStats(0, 10), vec![(accounts(1), U64(2))]);
+    }
+
+    #[test]
+    fn get_record_tree() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_root_record_id = create_random_hash_string();
+        log!("random_root_record_id{:?}",random_root_record_id);
+        let random_sub_record_id_1 = create_random_hash_string();
+        log!("random_sub_record_id_1{:?}",random_sub_record_id_1);
+        let random_sub_record_id_2 = create_random_hash_string();
+        log!("random_sub_record_id_2{:?}",random_sub_record_id_2);
+        let random_sub_sub_record_id = create_random_hash_string();
+        log!("random_sub_sub_record_id{:?}",random_sub_sub_record_id);
+
+        contract.createRecord(random_root_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        for (record_id, parent_record_id) in [(&random_sub_record_id_1, &random_root_record_id),
+            (&random_sub_record_id_2, &random_root_record_id),
+            (&random_sub_sub_record_id, &random_sub_record_id_1)] {
+            contract.createSubRecord(record_id.clone(),
+                                     parent_record_id.clone(),
+                                     create_random_hash_string(),
+                                     create_random_hash_string());
+        }
+
+        let result_ids: Vec<String> = contract.ancestors(random_sub_sub_record_id.clone(), 10)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        log!("result_ids{:?}",result_ids);
+
+        assert_eq!(result_ids, vec![random_sub_record_id_1.clone(), random_root_record_id.clone()]);
+        assert_eq!(contract.ancestors(random_sub_sub_record_id.clone(), 1).len(), 1);
+        assert_eq!(contract.ancestors(random_root_record_id.clone(), 10), vec![]);
+
+        let result: Vec<(String, u64)> = contract.descendants(random_root_record_id.clone(), 10, 10)
+            .into_iter()
+            .map(|tree_record| (tree_record.record.record_id, tree_record.depth))
+            .collect();
+
+        log!("result{:?}",result);
+
+        let expected = vec![
+            (random_sub_record_id_1.clone(), 1),
+            (random_sub_record_id_2.clone(), 1),
+            (random_sub_sub_record_id.clone(), 2),
+        ];
 
         assert_eq!(result, expected);
+        assert_eq!(contract.descendants(random_root_record_id.clone(), 1, 10).len(), 2);
+        assert_eq!(contract.descendants(random_root_record_id.clone(), 10, 1).len(), 1);
+        assert_eq!(contract.descendants(create_random_hash_string(), 10, 10), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Record can't be its own ancestor.")]
+    fn create_sub_record_closing_cycle() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id_1 = create_random_hash_string();
+        log!("random_record_id_1{:?}",random_record_id_1);
+        let random_record_id_2 = create_random_hash_string();
+        log!("random_record_id_2{:?}",random_record_id_2);
+
+        contract.setParentRecordRequired(false);
+        contract.createSubRecord(random_record_id_1.clone(),
+                                 random_record_id_2.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+        contract.createSubRecord(random_record_id_2.clone(),
+                                 random_record_id_1.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+    }
+
+    #[test]
+    fn get_record_tree_with_cycle() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        let random_record_id_1 = create_random_hash_string();
+        log!("random_record_id_1{:?}",random_record_id_1);
+        let random_record_id_2 = create_random_hash_string();
+        log!("random_record_id_2{:?}",random_record_id_2);
+
+        contract.createRecord(random_record_id_2.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createSubRecord(random_record_id_1.clone(),
+                                 random_record_id_2.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+
+        // New records can't close a cycle, link the records the way older state could
+        let mut record_2: ObjectRecord = RecheckRecords::get_record(&contract,
+            &RecheckRecords::string_to_hex_bytes(random_record_id_2.clone())).unwrap();
+        record_2.parentRecordId = RecheckRecords::string_to_hex_bytes(random_record_id_1.clone());
+        RecheckRecords::index_sub_record(&mut contract, &record_2);
+        RecheckRecords::save_record(&mut contract, record_2);
+
+        let result_ids: Vec<String> = contract.ancestors(random_record_id_1.clone(), 5)
+            .into_iter()
+            .map(|record| record.record_id)
+            .collect();
+
+        assert_eq!(result_ids, vec![random_record_id_2.clone(), random_record_id_1.clone()]);
+
+        let result = contract.descendants(random_record_id_1.clone(), 10, 10);
+
+        log!("result{:?}",result);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].record.record_id, random_record_id_2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Parent record must exist.")]
+    fn create_sub_record_without_parent() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        assert!(contract.parentRecordRequired());
+
+        contract.createSubRecord(create_random_hash_string(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+    }
+
+    #[test]
+    fn create_sub_records_batch_with_parents() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
+
+        contract.setMaxTreeDepth(2);
+
+        let random_root_record_id = create_random_hash_string();
+        log!("random_root_record_id{:?}",random_root_record_id);
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+        let random_sub_sub_record_id = create_random_hash_string();
+        log!("random_sub_sub_record_id{:?}",random_sub_sub_record_id);
+
+        let record_ids: Vec<(String, String)> = vec![
+            (random_root_record_id.clone(), random_root_record_id.clone()),
+            (random_sub_record_id.clone(), random_root_record_id.clone()),
+            (random_sub_sub_record_id.clone(), random_sub_record_id.clone()),
+            (create_random_hash_string(), random_sub_sub_record_id.clone()),
+            (create_random_hash_string(), create_random_hash_string()),
+        ];
+
+        let records: Vec<SubRecordWithExtrasInput> = record_ids.iter()
+            .map(|(record_id, parent_record_id)| SubRecordWithExtrasInput {
+                record_id_str: record_id.clone(),
+                parent_record_id_str: parent_record_id.clone(),
+                trail_str: create_random_hash_string(),
+                trail_signature_str: create_random_hash_string(),
+                extra_0_str: create_random_hash_string(),
+                extra_1_str: create_random_hash_string(),
+            })
+            .collect();
+
+        let results: Vec<Option<String>> = contract.createSubRecordsWithExtras2(records)
+            .into_iter()
+            .map(|result| result.error)
+            .collect();
+
+        log!("results{:?}",results);
+
+        let expected = vec![
+            None,
+            None,
+            None,
+            Some(String::from("Record tree is too deep.")),
+            Some(String::from("Parent record must exist.")),
+        ];
+
+        assert_eq!(results, expected);
+        assert_eq!(contract.maxTreeDepth(), 2);
     }
 
     #[test]
-    fn create_and_get_new_sub_record() {
+    fn get_orphans() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
@@ -402,89 +4873,103 @@ mod tests {
 
         let random_parent_record_id = create_random_hash_string();
         log!("random_parent_record_id{:?}",random_parent_record_id);
-        let random_parent_trail = create_random_hash_string();
-        log!("random_parent_trail{:?}",random_parent_trail);
-        let random_parent_trail_signature = create_random_hash_string();
-        log!("random_parent_trail_signature{:?}",random_parent_record_id);
+        let random_missing_parent_record_id = create_random_hash_string();
+        log!("random_missing_parent_record_id{:?}",random_missing_parent_record_id);
 
         contract.createRecord(random_parent_record_id.clone(),
-                              random_parent_trail.clone(),
-                              random_parent_trail_signature.clone());
-
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.createSubRecord(create_random_hash_string(),
+                                 random_parent_record_id.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
 
-        let random_sub_record_id = create_random_hash_string();
-        log!("random_sub_record_id{:?}",random_sub_record_id);
-        let random_sub_trail = create_random_hash_string();
-        log!("random_sub_trail{:?}",random_sub_trail);
-        let random_sub_trail_signature = create_random_hash_string();
-        log!("random_trail_str{:?}",random_parent_record_id);
+        contract.setParentRecordRequired(false);
 
-        contract.createSubRecord(random_sub_record_id.clone(),
-                                 random_parent_record_id.clone(),
-                                 random_sub_trail.clone(),
-                                 random_sub_trail_signature.clone());
+        for _ in 0..2 {
+            contract.createSubRecord(create_random_hash_string(),
+                                     random_missing_parent_record_id.clone(),
+                                     create_random_hash_string(),
+                                     create_random_hash_string());
+        }
 
-        let result = contract.subRecord(random_parent_record_id.clone(), 1);
+        let result = contract.orphans(0, 10);
 
         log!("result{:?}",result);
 
-        let expected = (
-            String::from(random_parent_record_id.clone()),
-            String::from(random_parent_record_id.clone()),
-            String::from(random_parent_trail.clone()),
-            String::from(random_parent_trail_signature.clone()),
-            accounts(1),
-            0,
-            1
-        );
+        let expected = vec![OrphanView {
+            parent_record_id: random_missing_parent_record_id.clone(),
+            sub_records_count: U64(2),
+        }];
 
         assert_eq!(result, expected);
+        assert_eq!(contract.orphans(0, 1), vec![]);
     }
 
     #[test]
-    fn create_and_get_new_record_with_extras_2() {
+    fn link_sub_records_by_policy() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
         testing_env!(context.is_view(false).build());
 
+        contract.addWriter(accounts(2), Role::Writer);
+        contract.addWriter(accounts(3), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, None);
+
         let random_record_id = create_random_hash_string();
         log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_parent_trail_signature{:?}",random_trail_signature);
-        let random_extra_0 = create_random_hash_string();
-        log!("random_extra_0{:?}",random_extra_0);
-        let random_extra_1 = create_random_hash_string();
-        log!("random_extra_1{:?}",random_extra_1);
 
-        contract.createSubRecordWithExtras2(random_record_id.clone(),
-                                            random_record_id.clone(),
-                                            random_trail.clone(),
-                                            random_trail_signature.clone(),
-                                            random_extra_0.clone(),
-                                            random_extra_1.clone());
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
 
-        let result = contract.records(random_record_id.clone());
+        assert_eq!(contract.linkPolicy(random_record_id.clone()), Some(LinkPolicy::CreatorOnly));
+        assert!(contract.canLinkSubRecord(random_record_id.clone(), accounts(2)));
+        assert!(!contract.canLinkSubRecord(random_record_id.clone(), accounts(3)));
 
-        log!("result{:?}",result);
+        contract.setLinkPolicy(random_record_id.clone(), LinkPolicy::Allowlist);
+        contract.addToLinkAllowlist(accounts(3));
 
-        let expected = (
-            String::from(random_record_id.clone()),
-            String::from(random_record_id.clone()),
-            String::from(random_trail.clone()),
-            String::from(random_trail_signature.clone()),
-            accounts(1),
-            0,
-            0
-        );
+        assert_eq!(contract.linkAllowlist(accounts(2), 0, 10), vec![accounts(3)]);
+        assert!(contract.canLinkSubRecord(random_record_id.clone(), accounts(3)));
+        assert!(!contract.canLinkSubRecord(random_record_id.clone(), accounts(4)));
 
-        assert_eq!(result, expected);
+        testing_env!(context.predecessor_account_id(accounts(3))
+            .signer_account_id(accounts(3))
+            .build());
+
+        contract.storage_deposit(None, None);
+
+        let random_sub_record_id = create_random_hash_string();
+        log!("random_sub_record_id{:?}",random_sub_record_id);
+
+        contract.createSubRecord(random_sub_record_id.clone(),
+                                 random_record_id.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
+
+        assert_eq!(contract.recordView(random_sub_record_id.clone()).unwrap().creator, accounts(3));
+
+        testing_env!(context.predecessor_account_id(accounts(1))
+            .signer_account_id(accounts(1))
+            .build());
+
+        contract.setLinkPolicy(random_record_id.clone(), LinkPolicy::Open);
+
+        assert!(contract.canLinkSubRecord(random_record_id.clone(), accounts(4)));
+        assert_eq!(contract.linkPolicy(create_random_hash_string()), None);
     }
 
     #[test]
-    fn create_and_verify_trail() {
+    #[should_panic(expected = "Not allowed to link sub records to the parent record.")]
+    fn link_sub_record_to_record_of_other_creator() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
@@ -492,256 +4977,231 @@ mod tests {
 
         let random_record_id = create_random_hash_string();
         log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_trail_str{:?}",random_record_id);
 
         contract.createRecord(random_record_id.clone(),
-                              random_trail.clone(),
-                              random_trail_signature.clone());
-
-        let result = contract.verifyTrail(random_trail.clone());
-
-        log!("result{:?}",result);
-
-        let expected = (
-            String::from(random_record_id.clone()),
-            String::from(random_record_id.clone()),
-            String::from(random_trail.clone()),
-            String::from(random_trail_signature.clone()),
-            accounts(1),
-            0,
-            0
-        );
-
-        assert_eq!(result, expected);
+                              create_random_hash_string(),
+                              create_random_hash_string());
+        contract.addWriter(accounts(2), Role::Writer);
+        contract.addToLinkAllowlist(accounts(3));
+        contract.setLinkPolicy(random_record_id.clone(), LinkPolicy::Allowlist);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, None);
+        contract.createSubRecord(create_random_hash_string(),
+                                 random_record_id.clone(),
+                                 create_random_hash_string(),
+                                 create_random_hash_string());
     }
 
     #[test]
-    fn create_and_verify_extra_0() {
+    fn set_link_policy_by_unregistered_owner() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
-        let mut contract = RecheckRecords::default();
+        let mut contract = RecheckRecords::new(accounts(4));
         testing_env!(context.is_view(false).build());
 
+        testing_env!(context.predecessor_account_id(accounts(4))
+            .signer_account_id(accounts(4))
+            .build());
+
+        contract.addWriter(accounts(2), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, None);
+
         let random_record_id = create_random_hash_string();
         log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_parent_trail_signature{:?}",random_trail_signature);
-        let random_extra_0 = create_random_hash_string();
-        log!("random_extra_0{:?}",random_extra_0);
-        let random_extra_1 = create_random_hash_string();
-        log!("random_extra_1{:?}",random_extra_1);
 
-        contract.createSubRecordWithExtras2(random_record_id.clone(),
-                                            random_record_id.clone(),
-                                            random_trail.clone(),
-                                            random_trail_signature.clone(),
-                                            random_extra_0.clone(),
-                                            random_extra_1.clone());
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
 
-        let result = contract.verifyExtra0(random_extra_0.clone());
+        testing_env!(context.predecessor_account_id(accounts(4))
+            .signer_account_id(accounts(4))
+            .attached_deposit(0)
+            .build());
 
-        log!("result{:?}",result);
+        contract.setLinkPolicy(random_record_id.clone(), LinkPolicy::Open);
 
-        let expected = (
-            String::from(random_record_id.clone()),
-            String::from(random_record_id.clone()),
-            String::from(random_trail.clone()),
-            String::from(random_trail_signature.clone()),
-            accounts(1),
-            0,
-            0
-        );
+        assert_eq!(contract.linkPolicy(random_record_id.clone()), Some(LinkPolicy::Open));
 
-        assert_eq!(result, expected);
+        contract.setLinkPolicy(random_record_id.clone(), LinkPolicy::CreatorOnly);
+
+        assert_eq!(contract.linkPolicy(random_record_id.clone()), Some(LinkPolicy::CreatorOnly));
+    }
+
+    fn sign_record_payload(secret_key: &near_crypto::SecretKey, payload: &SubRecordWithExtrasInput, nonce: u64) -> String {
+        let message: CryptoHash = RecheckRecords::signed_record_message(payload, nonce);
+        let near_crypto::Signature::ED25519(signature) = secret_key.sign(&message) else {
+            panic!("Signed records require an ed25519 key.");
+        };
+
+        return encode(signature.to_bytes());
+    }
+
+    fn create_random_payload() -> SubRecordWithExtrasInput {
+        let random_record_id = create_random_hash_string();
+
+        return SubRecordWithExtrasInput {
+            record_id_str: random_record_id.clone(),
+            parent_record_id_str: random_record_id,
+            trail_str: create_random_hash_string(),
+            trail_signature_str: create_random_hash_string(),
+            extra_0_str: create_random_hash_string(),
+            extra_1_str: create_random_hash_string(),
+        };
     }
 
     #[test]
-    fn create_and_verify_extra_1() {
+    fn create_signed_record() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
         testing_env!(context.is_view(false).build());
 
-        let random_record_id = create_random_hash_string();
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let public_key: PublicKey = secret_key.public_key().to_string().parse().unwrap();
+
+        let payload = create_random_payload();
+        let random_record_id = payload.record_id_str.clone();
         log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_parent_trail_signature{:?}",random_trail_signature);
-        let random_extra_0 = create_random_hash_string();
-        log!("random_extra_0{:?}",random_extra_0);
-        let random_extra_1 = create_random_hash_string();
-        log!("random_extra_1{:?}",random_extra_1);
+        let signature = sign_record_payload(&secret_key, &payload, 5);
 
-        contract.createSubRecordWithExtras2(random_record_id.clone(),
-                                            random_record_id.clone(),
-                                            random_trail.clone(),
-                                            random_trail_signature.clone(),
-                                            random_extra_0.clone(),
-                                            random_extra_1.clone());
+        contract.createRecordSigned(payload, public_key.clone(), signature, U64(5));
 
-        let result = contract.verifyExtra1(random_extra_1.clone());
+        let result = contract.recordView(random_record_id.clone()).unwrap();
 
         log!("result{:?}",result);
 
-        let expected = (
-            String::from(random_record_id.clone()),
-            String::from(random_record_id.clone()),
-            String::from(random_trail.clone()),
-            String::from(random_trail_signature.clone()),
-            accounts(1),
-            0,
-            0
-        );
-
-        assert_eq!(result, expected);
+        assert_eq!(result.author_public_key, Some(public_key.clone()));
+        assert_eq!(result.creator, accounts(1));
+        assert_eq!(contract.signedRecordNonce(public_key.clone()), U64(5));
     }
 
     #[test]
-    #[should_panic(expected = "Record must be unique.")]
-    fn create_non_unique_record() {
+    #[should_panic(expected = "Nonce was already used.")]
+    fn replay_signed_record() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
         testing_env!(context.is_view(false).build());
 
-        let random_record_id = create_random_hash_string();
-        log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_trail_str{:?}",random_record_id);
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let public_key: PublicKey = secret_key.public_key().to_string().parse().unwrap();
 
-        contract.createRecord(random_record_id.clone(),
-                              random_trail.clone(),
-                              random_trail_signature.clone());
+        let payload = create_random_payload();
+        let signature = sign_record_payload(&secret_key, &payload, 1);
 
-        let random_trail_2 = create_random_hash_string();
-        log!("random_trail_2{:?}",random_trail_2);
-        let random_trail_signature_2 = create_random_hash_string();
-        log!("random_trail_signature_2{:?}",random_trail_signature_2);
+        contract.createRecordSigned(payload, public_key.clone(), signature, U64(1));
 
-        contract.createRecord(random_record_id.clone(),
-                              random_trail_2.clone(),
-                              random_trail_signature_2.clone());
+        let payload = create_random_payload();
+        let signature = sign_record_payload(&secret_key, &payload, 1);
+
+        contract.createRecordSigned(payload, public_key.clone(), signature, U64(1));
     }
 
     #[test]
-    fn get_non_existing_record() {
+    #[should_panic(expected = "Only authorized writers can create records.")]
+    fn create_signed_record_by_unauthorized_relayer() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
-        let contract = RecheckRecords::default();
-        testing_env!(context.is_view(true).build());
+        let mut contract = RecheckRecords::default();
+        testing_env!(context.is_view(false).build());
 
-        let random_record_id = create_random_hash_string();
-        log!("random_record_id{:?}",random_record_id);
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let public_key: PublicKey = secret_key.public_key().to_string().parse().unwrap();
 
-        let result = contract.records(random_record_id);
-        log!("result{:?}",result);
+        let payload = create_random_payload();
+        let signature = sign_record_payload(&secret_key, &payload, 1);
 
-        let expected = RecheckRecords::null_record();
+        testing_env!(context.predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2))
+            .build());
 
-        assert_eq!(result, expected);
+        contract.createRecordSigned(payload, public_key.clone(), signature, U64(1));
     }
 
     #[test]
-    fn verify_wrong_trail() {
+    #[should_panic(expected = "Invalid payload signature.")]
+    fn create_signed_record_with_other_nonce() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
         testing_env!(context.is_view(false).build());
 
-        let random_record_id = create_random_hash_string();
-        log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_trail_str{:?}",random_record_id);
-
-        contract.createRecord(random_record_id.clone(),
-                              random_trail.clone(),
-                              random_trail_signature.clone());
-
-        let result = contract.verifyTrail(random_trail_signature.clone());
-
-        log!("result{:?}",result);
+        let secret_key = near_crypto::SecretKey::from_random(near_crypto::KeyType::ED25519);
+        let public_key: PublicKey = secret_key.public_key().to_string().parse().unwrap();
 
-        let expected = RecheckRecords::null_record();
+        let payload = create_random_payload();
+        let signature = sign_record_payload(&secret_key, &payload, 1);
 
-        assert_eq!(result, expected);
+        contract.createRecordSigned(payload, public_key.clone(), signature, U64(2));
     }
 
     #[test]
-    fn verify_wrong_extra_0() {
+    fn create_record_by_calling_contract() {
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = RecheckRecords::default();
         testing_env!(context.is_view(false).build());
 
+        contract.addWriter(accounts(3), Role::Writer);
+
+        testing_env!(context.predecessor_account_id(accounts(3))
+            .signer_account_id(accounts(2))
+            .attached_deposit(ONE_NEAR)
+            .build());
+
+        contract.storage_deposit(None, None);
+
         let random_record_id = create_random_hash_string();
         log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_parent_trail_signature{:?}",random_trail_signature);
-        let random_extra_0 = create_random_hash_string();
-        log!("random_extra_0{:?}",random_extra_0);
-        let random_extra_1 = create_random_hash_string();
-        log!("random_extra_1{:?}",random_extra_1);
 
-        contract.createSubRecordWithExtras2(random_record_id.clone(),
-                                            random_record_id.clone(),
-                                            random_trail.clone(),
-                                            random_trail_signature.clone(),
-                                            random_extra_0.clone(),
-                                            random_extra_1.clone());
+        contract.createRecord(random_record_id.clone(),
+                              create_random_hash_string(),
+                              create_random_hash_string());
 
-        let result = contract.verifyExtra0(random_extra_1.clone());
+        let result = contract.recordView(random_record_id.clone()).unwrap();
 
         log!("result{:?}",result);
 
-        let expected = RecheckRecords::null_record();
-
-        assert_eq!(result, expected);
+        assert_eq!(result.creator, accounts(3));
+        assert_eq!(result.signer, accounts(2));
+        assert_eq!(contract.recordsByCreator(accounts(3), 0, 10).len(), 1);
+        assert_eq!(contract.storage_balance_of(accounts(2)), None);
+        assert!(contract.canLinkSubRecord(random_record_id.clone(), accounts(3)));
+        assert!(!contract.canLinkSubRecord(random_record_id.clone(), accounts(2)));
     }
 
     #[test]
-    fn verify_wrong_extra_1() {
+    fn get_v1_record_signer() {
         let mut context = get_context(accounts(1));
-        testing_env!(context.build());
-        let mut contract = RecheckRecords::default();
         testing_env!(context.is_view(false).build());
 
         let random_record_id = create_random_hash_string();
         log!("random_record_id{:?}",random_record_id);
-        let random_trail = create_random_hash_string();
-        log!("random_trail{:?}",random_trail);
-        let random_trail_signature = create_random_hash_string();
-        log!("random_parent_trail_signature{:?}",random_trail_signature);
-        let random_extra_0 = create_random_hash_string();
-        log!("random_extra_0{:?}",random_extra_0);
-        let random_extra_1 = create_random_hash_string();
-        log!("random_extra_1{:?}",random_extra_1);
 
-        contract.createSubRecordWithExtras2(random_record_id.clone(),
-                                            random_record_id.clone(),
-                                            random_trail.clone(),
-                                            random_trail_signature.clone(),
-                                            random_extra_0.clone(),
-                                            random_extra_1.clone());
+        create_v1_state(RecheckRecords::string_to_hex_bytes(random_record_id.clone()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()),
+                        RecheckRecords::string_to_hex_bytes(create_random_hash_string()));
 
-        let result = contract.verifyExtra1(random_extra_0.clone());
+        let contract = RecheckRecords::migrate();
 
-        log!("result{:?}",result);
+        let result = contract.recordView(random_record_id.clone()).unwrap();
 
-        let expected = RecheckRecords::null_record();
+        log!("result{:?}",result);
 
-        assert_eq!(result, expected);
+        assert_eq!(result.creator, accounts(1));
+        assert_eq!(result.signer, accounts(1));
     }
 
     #[test]